@@ -0,0 +1,230 @@
+// Signed Attestations
+// The canonical payload every DGE signature covers: D-Metric readings, price feeds, and
+// grant completion certificates alike. Each payload is domain-separated: it opens with a fixed tag and the kind of
+// statement, and is bound to one chain (by genesis hash), one subject (the proposal or grant
+// id), and an expiry. A signature therefore cannot be replayed on another chain, for another
+// grant, as a different kind of statement, or after it lapses.
+//
+// The layout is SCALE, like the evidence schema: little-endian integers, raw byte arrays,
+// and the statement as an enum (one index byte, then its fields). As with certificates,
+// signature schemes are plugged in through `SignatureVerifier`.
+
+use crate::certificate::SignatureVerifier;
+use crate::evidence::{compact_len, Attestation, MetricSnapshot, Writer, MAX_EVIDENCE_ARTIFACTS};
+
+/// Prefix of every attestation payload, separating it from any other message a key may sign.
+pub const ATTESTATION_DOMAIN_TAG: [u8; 16] = *b"dge:attestation1";
+
+/// Length of the largest payload: a metric statement with `MAX_EVIDENCE_ARTIFACTS` artifacts.
+pub const MAX_ATTESTATION_PAYLOAD_LEN: usize = 16 + 32 + 32 + 1 + (1 + 8 + 8 + 1 + 32 * MAX_EVIDENCE_ARTIFACTS) + 8;
+
+/// What an attester vouches for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Statement<'a> {
+    /// A milestone's metric reading, scored into its D-Metric, with the hashes of the
+    /// artifacts backing it.
+    Metric { milestone_index: u8, metric: MetricSnapshot, artifact_hashes: &'a [[u8; 32]] },
+    /// A token price in USD, scaled by 10^18, for bond sizing.
+    Price { price_in_usd_scaled: u128, observed_at: u64 },
+    /// A grant completed; signed by the issuing chain for its `CompletionCertificate`.
+    Completion { builder: [u8; 32], total_grant_amount: u64, amount_disbursed: u64, final_score: u8, completed_at: u64 },
+}
+
+/// The fields an attester signs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AttestationPayload<'a> {
+    /// Genesis hash of the chain the attestation is meant for.
+    pub genesis_hash: [u8; 32],
+    /// The proposal or grant the statement is about (the Grant PDA on Solana).
+    pub subject: [u8; 32],
+    pub statement: Statement<'a>,
+    /// Unix timestamp (seconds) after which the attestation is no longer accepted.
+    pub expires_at: u64,
+}
+
+/// Reasons an attestation is rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttestationError {
+    /// Signed for a different chain than the verifier expects.
+    WrongChain,
+    /// Presented after `expires_at`.
+    Expired,
+    /// The signature does not match the attester over this payload.
+    InvalidSignature,
+}
+
+impl Statement<'_> {
+    fn index(&self) -> u8 {
+        match self {
+            Self::Metric { .. } => 0,
+            Self::Price { .. } => 1,
+            Self::Completion { .. } => 2,
+        }
+    }
+}
+
+impl AttestationPayload<'_> {
+    /// Size of `encode`'s output, or `None` if a length is too large to encode.
+    pub fn encoded_len(&self) -> Option<usize> {
+        let statement = match self.statement {
+            Statement::Metric { artifact_hashes, .. } => {
+                1 + 8 + 8 + compact_len(artifact_hashes.len())? + 32 * artifact_hashes.len()
+            }
+            Statement::Price { .. } => 16 + 8,
+            Statement::Completion { .. } => 32 + 8 + 8 + 1 + 8,
+        };
+        Some(16 + 32 + 32 + 1 + statement + 8)
+    }
+
+    /// SCALE-encodes the payload into `out`, domain tag first. This is the exact message
+    /// attesters sign. Returns the bytes written, or `None` if `out` is too small.
+    pub fn encode(&self, out: &mut [u8]) -> Option<usize> {
+        let mut writer = Writer { out, position: 0 };
+        writer.write(&ATTESTATION_DOMAIN_TAG)?;
+        writer.write(&self.genesis_hash)?;
+        writer.write(&self.subject)?;
+        writer.write(&[self.statement.index()])?;
+        match self.statement {
+            Statement::Metric { milestone_index, metric, artifact_hashes } => {
+                writer.write(&[milestone_index])?;
+                writer.write(&metric.value.to_le_bytes())?;
+                writer.write(&metric.observed_at.to_le_bytes())?;
+                writer.write_compact_len(artifact_hashes.len())?;
+                for hash in artifact_hashes {
+                    writer.write(hash)?;
+                }
+            }
+            Statement::Price { price_in_usd_scaled, observed_at } => {
+                writer.write(&price_in_usd_scaled.to_le_bytes())?;
+                writer.write(&observed_at.to_le_bytes())?;
+            }
+            Statement::Completion { builder, total_grant_amount, amount_disbursed, final_score, completed_at } => {
+                writer.write(&builder)?;
+                writer.write(&total_grant_amount.to_le_bytes())?;
+                writer.write(&amount_disbursed.to_le_bytes())?;
+                writer.write(&[final_score])?;
+                writer.write(&completed_at.to_le_bytes())?;
+            }
+        }
+        writer.write(&self.expires_at.to_le_bytes())?;
+        Some(writer.position)
+    }
+}
+
+/// Verifies one attester's signature over `payload`: the payload must target the expected
+/// chain, must not have expired at `now` (Unix seconds), and must carry a valid signature.
+/// Payloads longer than `MAX_ATTESTATION_PAYLOAD_LEN` never verify.
+pub fn verify_attestation(
+    payload: &AttestationPayload,
+    attestation: &Attestation,
+    expected_genesis_hash: &[u8; 32],
+    now: u64,
+    verifier: &impl SignatureVerifier,
+) -> Result<(), AttestationError> {
+    if payload.genesis_hash != *expected_genesis_hash {
+        return Err(AttestationError::WrongChain);
+    }
+    if now > payload.expires_at {
+        return Err(AttestationError::Expired);
+    }
+
+    let mut message = [0u8; MAX_ATTESTATION_PAYLOAD_LEN];
+    let len = payload.encode(&mut message).ok_or(AttestationError::InvalidSignature)?;
+    if !verifier.verify(&message[..len], &attestation.attester, &attestation.signature) {
+        return Err(AttestationError::InvalidSignature);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{sign, MockVerifier};
+
+    const GENESIS: [u8; 32] = [7; 32];
+    const NOW: u64 = 1_700_000_000;
+
+    fn sign_payload(payload: &AttestationPayload, attester: [u8; 32]) -> Attestation {
+        let mut message = [0u8; MAX_ATTESTATION_PAYLOAD_LEN];
+        let len = payload.encode(&mut message).unwrap();
+        Attestation { attester, signature: sign(&message[..len], &attester) }
+    }
+
+    fn metric_payload() -> AttestationPayload<'static> {
+        AttestationPayload {
+            genesis_hash: GENESIS,
+            subject: [1; 32],
+            statement: Statement::Metric {
+                milestone_index: 2,
+                metric: MetricSnapshot { value: 1_000, observed_at: NOW },
+                artifact_hashes: &[[0xaa; 32]],
+            },
+            expires_at: NOW + 600,
+        }
+    }
+
+    #[test]
+    // Test case: The payload matches SCALE byte for byte, behind the domain tag.
+    fn test_encoding() {
+        let payload = metric_payload();
+        let mut buffer = [0u8; MAX_ATTESTATION_PAYLOAD_LEN];
+        let len = payload.encode(&mut buffer).unwrap();
+        assert_eq!(Some(len), payload.encoded_len());
+        assert_eq!(len, 16 + 32 + 32 + 1 + 17 + 33 + 8);
+        assert_eq!(buffer[..16], *b"dge:attestation1");
+        assert_eq!(buffer[16..48], GENESIS);
+        assert_eq!(buffer[48..80], [1; 32]);
+        assert_eq!(buffer[80], 0); // Statement::Metric
+        assert_eq!(buffer[81], 2);
+        assert_eq!(buffer[82..90], 1_000u64.to_le_bytes());
+        assert_eq!(buffer[98], 1 << 2); // compact(1)
+        assert_eq!(buffer[99..131], [0xaa; 32]);
+        assert_eq!(buffer[131..139], (NOW + 600).to_le_bytes());
+
+        let price = AttestationPayload {
+            statement: Statement::Price { price_in_usd_scaled: 1_500_000_000_000_000_000, observed_at: NOW },
+            ..payload
+        };
+        assert_eq!(price.encode(&mut buffer), Some(16 + 32 + 32 + 1 + 24 + 8));
+        assert_eq!(buffer[80], 1); // Statement::Price
+        assert_eq!(buffer[81..97], 1_500_000_000_000_000_000u128.to_le_bytes());
+        assert_eq!(payload.encode(&mut buffer[..len - 1]), None);
+    }
+
+    #[test]
+    // Test case: A valid attestation verifies; replays to another chain, subject, or
+    // statement kind, and late submissions are rejected.
+    fn test_verify_and_replay_protection() {
+        let payload = metric_payload();
+        let attestation = sign_payload(&payload, [9; 32]);
+        assert_eq!(verify_attestation(&payload, &attestation, &GENESIS, NOW, &MockVerifier), Ok(()));
+        assert_eq!(verify_attestation(&payload, &attestation, &GENESIS, NOW + 600, &MockVerifier), Ok(()));
+
+        assert_eq!(
+            verify_attestation(&payload, &attestation, &GENESIS, NOW + 601, &MockVerifier),
+            Err(AttestationError::Expired)
+        );
+        let other_chain = AttestationPayload { genesis_hash: [8; 32], ..payload };
+        assert_eq!(
+            verify_attestation(&other_chain, &attestation, &[8; 32], NOW, &MockVerifier),
+            Err(AttestationError::InvalidSignature)
+        );
+        assert_eq!(
+            verify_attestation(&payload, &attestation, &[8; 32], NOW, &MockVerifier),
+            Err(AttestationError::WrongChain)
+        );
+        let other_grant = AttestationPayload { subject: [2; 32], ..payload };
+        assert_eq!(
+            verify_attestation(&other_grant, &attestation, &GENESIS, NOW, &MockVerifier),
+            Err(AttestationError::InvalidSignature)
+        );
+        let as_price = AttestationPayload {
+            statement: Statement::Price { price_in_usd_scaled: 1_000, observed_at: NOW },
+            ..payload
+        };
+        assert_eq!(
+            verify_attestation(&as_price, &attestation, &GENESIS, NOW, &MockVerifier),
+            Err(AttestationError::InvalidSignature)
+        );
+    }
+}
@@ -0,0 +1,290 @@
+// Builder Bond (BB)
+// Sizes the Builder Bond, the builder's economic commitment ("skin in the game"),
+// in FST tokens from its USD value and the current FST price.
+
+use crate::fixed_point::{mul_div, Rounding, UFixed18};
+
+// --- Builder Bond (BB) Constants ---
+
+/// The required Builder Bond amount for a Tier 1 (Community) App, in USD equivalent.
+pub const TIER_1_BUILDER_BOND_USD: u64 = 300;
+
+/// The required Builder Bond amount for a Tier 2 (Growth) App, in USD equivalent.
+pub const TIER_2_BUILDER_BOND_USD: u64 = 1_500;
+
+/// The required Builder Bond amount for a Tier 3 (Ecosystem) App, in USD equivalent.
+pub const TIER_3_BUILDER_BOND_USD: u64 = 7_500;
+
+/// The smallest Tier 2 (Growth) grant, in whole tokens of the grant currency.
+pub const TIER_2_MIN_GRANT_TOKENS: u64 = 10_000;
+
+/// The smallest Tier 3 (Ecosystem) grant, in whole tokens of the grant currency.
+pub const TIER_3_MIN_GRANT_TOKENS: u64 = 100_000;
+
+/// Rounding applied when converting a bond into token base units.
+/// Always up: the protocol must never accept a bond worth less than its USD requirement.
+pub const BOND_ROUNDING: Rounding = Rounding::Ceil;
+
+/// The largest token decimal count supported (10^38 is the biggest power of ten in a u128).
+pub const MAX_TOKEN_DECIMALS: u8 = 38;
+
+/// Haircut denominator: 10_000 bps means collateral is credited at 100% of its USD value.
+pub const HAIRCUT_BPS_DENOMINATOR: u64 = 10_000;
+
+/// Reasons a bond amount cannot be computed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BondError {
+    /// The oracle reported a zero price.
+    ZeroPrice,
+    /// An intermediate value exceeded the u128 range.
+    Overflow,
+    /// The final amount does not fit in a u64.
+    Conversion,
+    /// The token's decimal count exceeds `MAX_TOKEN_DECIMALS`.
+    InvalidDecimals,
+}
+
+/// Grant tiers. Larger, more strategic grants demand a proportionally larger bond.
+/// The discriminant is the on-chain tier number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum GrantTier {
+    Community = 1,
+    Growth = 2,
+    Ecosystem = 3,
+}
+
+impl GrantTier {
+    /// Parses an on-chain tier number, rejecting anything outside the table.
+    pub fn from_u8(tier: u8) -> Option<Self> {
+        match tier {
+            1 => Some(Self::Community),
+            2 => Some(Self::Growth),
+            3 => Some(Self::Ecosystem),
+            _ => None,
+        }
+    }
+
+    /// The tier of a grant of `total_grant_amount`, given the smallest Tier 2 and Tier 3 grants
+    /// in the same units. Grants below the Tier 2 minimum are Tier 1.
+    pub fn for_grant_amount(total_grant_amount: u64, tier_min_grant_amount: [u64; 2]) -> Self {
+        if total_grant_amount >= tier_min_grant_amount[1] {
+            Self::Ecosystem
+        } else if total_grant_amount >= tier_min_grant_amount[0] {
+            Self::Growth
+        } else {
+            Self::Community
+        }
+    }
+
+    /// The Builder Bond for this tier, in USD equivalent.
+    pub fn bond_usd(self) -> u64 {
+        match self {
+            Self::Community => TIER_1_BUILDER_BOND_USD,
+            Self::Growth => TIER_2_BUILDER_BOND_USD,
+            Self::Ecosystem => TIER_3_BUILDER_BOND_USD,
+        }
+    }
+}
+
+/// Calculates the amount of $FST tokens required for the Tier 1 Builder Bond.
+///
+/// This function assumes the protocol can retrieve the current market price of $FST in USD.
+/// This would typically require an Oracle feed (e.g., Pyth Network, Chainlink) in a live deployment.
+///
+/// # Arguments
+/// * `fst_price_in_usd_scaled` - The current price of 1 $FST token, scaled by 10^18.
+///   (e.g., if FST is $1.50, this value is 1_500_000_000_000_000_000)
+///
+/// # Returns
+/// * The amount of $FST tokens (as a whole token amount) required for the bond.
+pub fn get_builder_bond_amount(fst_price_in_usd_scaled: u128) -> Result<u64, BondError> {
+    get_bond_for_tier(GrantTier::Community, fst_price_in_usd_scaled)
+}
+
+/// Calculates the amount of $FST tokens required for the Builder Bond of the given tier,
+/// rounded down to whole tokens.
+pub fn get_bond_for_tier(tier: GrantTier, fst_price_in_usd_scaled: u128) -> Result<u64, BondError> {
+    // Bond Amount (FST) = Bond Value USD / FST Price USD, rounded down to whole tokens.
+
+    if fst_price_in_usd_scaled == 0 {
+        // Prevent division by zero if the token price is somehow zero
+        return Err(BondError::ZeroPrice);
+    }
+
+    let bond_usd = UFixed18::from_integer(tier.bond_usd() as u128).ok_or(BondError::Overflow)?;
+    let fst_amount = bond_usd
+        .checked_div(UFixed18::from_raw(fst_price_in_usd_scaled), Rounding::Floor)
+        .ok_or(BondError::Overflow)?;
+
+    // Un-scale the final FST token amount and convert to u64 for return
+    fst_amount.to_integer(Rounding::Floor).try_into().map_err(|_| BondError::Conversion)
+}
+
+/// Calculates the Builder Bond in token base units (the smallest indivisible unit,
+/// like lamports for SOL), rounded up so the bond always covers its full USD value.
+///
+/// # Arguments
+/// * `fst_price_in_usd_scaled` - The current price of 1 $FST token, scaled by 10^18.
+/// * `token_decimals` - The FST mint's decimal count (e.g., 9 for SPL-style tokens).
+///
+/// # Returns
+/// * The bond as a raw base-unit amount, ready to be used in a token transfer.
+pub fn get_builder_bond_amount_in_base_units(fst_price_in_usd_scaled: u128, token_decimals: u8) -> Result<u64, BondError> {
+    get_bond_for_tier_in_base_units(GrantTier::Community, fst_price_in_usd_scaled, token_decimals)
+}
+
+/// Calculates the Builder Bond of the given tier in token base units, rounded up.
+pub fn get_bond_for_tier_in_base_units(tier: GrantTier, fst_price_in_usd_scaled: u128, token_decimals: u8) -> Result<u64, BondError> {
+    usd_to_token_base_units(tier.bond_usd(), fst_price_in_usd_scaled, token_decimals, BOND_ROUNDING)
+}
+
+/// Converts a whole-dollar amount into token base units at the given price, using an
+/// explicit rounding direction for the final base unit.
+pub fn usd_to_token_base_units(
+    amount_usd: u64,
+    token_price_in_usd_scaled: u128,
+    token_decimals: u8,
+    rounding: Rounding,
+) -> Result<u64, BondError> {
+    if token_price_in_usd_scaled == 0 {
+        return Err(BondError::ZeroPrice);
+    }
+    if token_decimals > MAX_TOKEN_DECIMALS {
+        return Err(BondError::InvalidDecimals);
+    }
+
+    // Base Units = (USD Amount / Token Price USD) * 10^decimals, with a single rounding step.
+    let amount_usd = UFixed18::from_integer(amount_usd as u128).ok_or(BondError::Overflow)?;
+    let base_units_per_token = 10u128.pow(token_decimals as u32);
+    let base_units = mul_div(amount_usd.raw(), base_units_per_token, token_price_in_usd_scaled, rounding)
+        .ok_or(BondError::Overflow)?;
+
+    base_units.try_into().map_err(|_| BondError::Conversion)
+}
+
+/// Scales a bond requirement by a collateral haircut, rounded up.
+/// Volatile collateral posts more than its face value: at 13_000 bps a bond needs 130%.
+pub fn apply_collateral_haircut(amount: u64, haircut_bps: u16) -> Result<u64, BondError> {
+    let scaled = mul_div(amount as u128, haircut_bps as u128, HAIRCUT_BPS_DENOMINATOR as u128, BOND_ROUNDING)
+        .ok_or(BondError::Overflow)?;
+    scaled.try_into().map_err(|_| BondError::Conversion)
+}
+
+/// Escalates a bond for a builder's prior liquidations, rounded up. Each prior liquidation
+/// adds `escalation_bps` to the 100% multiplier, up to `max_multiplier_bps` (never below 100%):
+/// at 5_000 bps per liquidation, a builder liquidated twice posts 200% of the bond.
+pub fn apply_repeat_offender_escalation(
+    amount: u64,
+    prior_liquidations: u32,
+    escalation_bps: u16,
+    max_multiplier_bps: u16,
+) -> Result<u64, BondError> {
+    let multiplier_bps = (HAIRCUT_BPS_DENOMINATOR + escalation_bps as u64 * prior_liquidations as u64)
+        .min((max_multiplier_bps as u64).max(HAIRCUT_BPS_DENOMINATOR));
+    let scaled = mul_div(amount as u128, multiplier_bps as u128, HAIRCUT_BPS_DENOMINATOR as u128, BOND_ROUNDING)
+        .ok_or(BondError::Overflow)?;
+    scaled.try_into().map_err(|_| BondError::Conversion)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixed_point::SCALE;
+
+    #[test]
+    // Test case: FST token price is $1.00. Bond should be 300 FST.
+    fn test_bond_price_one_dollar() {
+        assert_eq!(get_builder_bond_amount(SCALE), Ok(300));
+    }
+
+    #[test]
+    // Test case: FST token price is $1.50. Bond should be 200 FST.
+    fn test_bond_price_one_fifty() {
+        assert_eq!(get_builder_bond_amount(SCALE * 3 / 2), Ok(200));
+    }
+
+    #[test]
+    // Test case: FST token price is zero. The oracle error is surfaced.
+    fn test_bond_zero_price() {
+        assert_eq!(get_builder_bond_amount(0), Err(BondError::ZeroPrice));
+        assert_eq!(get_builder_bond_amount_in_base_units(0, 9), Err(BondError::ZeroPrice));
+    }
+
+    #[test]
+    // Test case: FST token price is $1.50 with 9 decimals. Bond divides evenly: 200 FST.
+    fn test_bond_base_units_exact() {
+        assert_eq!(get_builder_bond_amount_in_base_units(SCALE * 3 / 2, 9), Ok(200_000_000_000));
+    }
+
+    #[test]
+    // Test case: FST token price is $0.70 with 6 decimals. 428.5714285... FST rounds up.
+    fn test_bond_base_units_round_up() {
+        let price = 700_000_000_000_000_000;
+        assert_eq!(get_builder_bond_amount_in_base_units(price, 6), Ok(428_571_429));
+        assert_eq!(usd_to_token_base_units(TIER_1_BUILDER_BOND_USD, price, 6, Rounding::Floor), Ok(428_571_428));
+        // The whole-token API truncates the same bond to 428 FST.
+        assert_eq!(get_builder_bond_amount(price), Ok(428));
+    }
+
+    #[test]
+    // Test case: FST token price is $1.50. Each tier's bond scales with its USD value.
+    fn test_bond_for_each_tier() {
+        let price = SCALE * 3 / 2;
+        assert_eq!(get_bond_for_tier(GrantTier::Community, price), Ok(200));
+        assert_eq!(get_bond_for_tier(GrantTier::Growth, price), Ok(1_000));
+        assert_eq!(get_bond_for_tier(GrantTier::Ecosystem, price), Ok(5_000));
+        assert_eq!(get_bond_for_tier_in_base_units(GrantTier::Ecosystem, price, 9), Ok(5_000_000_000_000));
+    }
+
+    #[test]
+    // Test case: Only tiers 1-3 exist.
+    fn test_tier_parsing() {
+        assert_eq!(GrantTier::from_u8(0), None);
+        assert_eq!(GrantTier::from_u8(2), Some(GrantTier::Growth));
+        assert_eq!(GrantTier::from_u8(4), None);
+        assert_eq!(GrantTier::Ecosystem as u8, 3);
+    }
+
+    #[test]
+    // Test case: The tier follows the grant amount, with each minimum belonging to its tier.
+    fn test_tier_for_grant_amount() {
+        let minimums = [TIER_2_MIN_GRANT_TOKENS, TIER_3_MIN_GRANT_TOKENS];
+        assert_eq!(GrantTier::for_grant_amount(0, minimums), GrantTier::Community);
+        assert_eq!(GrantTier::for_grant_amount(TIER_2_MIN_GRANT_TOKENS - 1, minimums), GrantTier::Community);
+        assert_eq!(GrantTier::for_grant_amount(TIER_2_MIN_GRANT_TOKENS, minimums), GrantTier::Growth);
+        assert_eq!(GrantTier::for_grant_amount(TIER_3_MIN_GRANT_TOKENS - 1, minimums), GrantTier::Growth);
+        assert_eq!(GrantTier::for_grant_amount(u64::MAX, minimums), GrantTier::Ecosystem);
+    }
+
+    #[test]
+    // Test case: A 130% haircut on a $300 bond at $1.00 and 6 decimals demands 390 tokens.
+    fn test_collateral_haircut() {
+        let bond = get_builder_bond_amount_in_base_units(SCALE, 6).unwrap();
+        assert_eq!(apply_collateral_haircut(bond, 10_000), Ok(300_000_000));
+        assert_eq!(apply_collateral_haircut(bond, 13_000), Ok(390_000_000));
+        // Fractional base units round up.
+        assert_eq!(apply_collateral_haircut(1, 13_000), Ok(2));
+        assert_eq!(apply_collateral_haircut(u64::MAX, 20_000), Err(BondError::Conversion));
+    }
+
+    #[test]
+    // Test case: A 300 FST bond at +50% per prior liquidation, capped at 250%.
+    fn test_repeat_offender_escalation() {
+        assert_eq!(apply_repeat_offender_escalation(300, 0, 5_000, 25_000), Ok(300));
+        assert_eq!(apply_repeat_offender_escalation(300, 1, 5_000, 25_000), Ok(450));
+        assert_eq!(apply_repeat_offender_escalation(300, 2, 5_000, 25_000), Ok(600));
+        assert_eq!(apply_repeat_offender_escalation(300, u32::MAX, 5_000, 25_000), Ok(750));
+        // Fractional base units round up; a cap below 100% never discounts the bond.
+        assert_eq!(apply_repeat_offender_escalation(1, 1, 5_000, 25_000), Ok(2));
+        assert_eq!(apply_repeat_offender_escalation(300, 1, 5_000, 5_000), Ok(300));
+        assert_eq!(apply_repeat_offender_escalation(u64::MAX, 1, 5_000, 25_000), Err(BondError::Conversion));
+    }
+
+    #[test]
+    // Test case: Out-of-range decimals and amounts are rejected instead of wrapping.
+    fn test_bond_base_units_limits() {
+        assert_eq!(get_builder_bond_amount_in_base_units(SCALE, 39), Err(BondError::InvalidDecimals));
+        assert_eq!(get_builder_bond_amount_in_base_units(1, 18), Err(BondError::Conversion));
+    }
+}
@@ -0,0 +1,213 @@
+// Grant Completion Certificates
+// A portable credential stating that a builder delivered every milestone of a grant: the grant,
+// the builder, the amounts paid, the final D-Metric score, and the chain it happened on (by
+// genesis hash). Certificates travel SCALE-encoded at a fixed length. The issuing chain's
+// attestation key does not sign those bytes but the certificate's domain-tagged
+// `AttestationPayload` (a completion statement about the grant, see `attestation.rs`), so
+// third parties can check them offline without a node and the signature cannot be passed
+// off as any other kind of DGE attestation.
+//
+// dge-core carries no cryptography; callers plug in the signature scheme of the issuing chain
+// (ed25519, sr25519) through `SignatureVerifier`.
+
+use crate::attestation::{AttestationPayload, Statement, MAX_ATTESTATION_PAYLOAD_LEN};
+use crate::scoring::MAX_D_METRIC_SCORE;
+
+/// Current certificate format version.
+pub const CERTIFICATE_VERSION: u8 = 1;
+
+/// Length of an encoded certificate: version, genesis hash, grant id, builder, two amounts,
+/// final score, and completion timestamp.
+pub const CERTIFICATE_ENCODED_LEN: usize = 1 + 32 + 32 + 32 + 8 + 8 + 1 + 8;
+
+/// The certified facts about a completed grant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompletionCertificate {
+    pub version: u8,
+    /// Genesis hash of the issuing chain.
+    pub genesis_hash: [u8; 32],
+    /// The grant's identifier on the issuing chain (the Grant PDA on Solana).
+    pub grant_id: [u8; 32],
+    pub builder: [u8; 32],
+    pub total_grant_amount: u64,
+    pub amount_disbursed: u64,
+    pub final_score: u8,
+    /// Unix timestamp (seconds) of the final payout.
+    pub completed_at: u64,
+}
+
+/// Reasons a certificate is rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CertificateError {
+    /// Wrong length, or fields that contradict each other.
+    Malformed,
+    /// A format version this library does not understand.
+    UnsupportedVersion,
+    /// Issued on a different chain than the verifier expects.
+    WrongChain,
+    /// The signature does not match the issuer key.
+    InvalidSignature,
+}
+
+/// A signature scheme used to check attestation and certificate signatures.
+pub trait SignatureVerifier {
+    /// Returns true if `signature` is `public_key`'s valid signature over `message`.
+    fn verify(&self, message: &[u8], public_key: &[u8; 32], signature: &[u8; 64]) -> bool;
+}
+
+impl CompletionCertificate {
+    /// The payload the issuer signs: a completion statement about `grant_id` that never expires.
+    pub fn attestation_payload(&self) -> AttestationPayload<'static> {
+        AttestationPayload {
+            genesis_hash: self.genesis_hash,
+            subject: self.grant_id,
+            statement: Statement::Completion {
+                builder: self.builder,
+                total_grant_amount: self.total_grant_amount,
+                amount_disbursed: self.amount_disbursed,
+                final_score: self.final_score,
+                completed_at: self.completed_at,
+            },
+            expires_at: u64::MAX,
+        }
+    }
+
+    /// SCALE-encodes the certificate for transport.
+    pub fn encode(&self) -> [u8; CERTIFICATE_ENCODED_LEN] {
+        let mut out = [0u8; CERTIFICATE_ENCODED_LEN];
+        out[0] = self.version;
+        out[1..33].copy_from_slice(&self.genesis_hash);
+        out[33..65].copy_from_slice(&self.grant_id);
+        out[65..97].copy_from_slice(&self.builder);
+        out[97..105].copy_from_slice(&self.total_grant_amount.to_le_bytes());
+        out[105..113].copy_from_slice(&self.amount_disbursed.to_le_bytes());
+        out[113] = self.final_score;
+        out[114..122].copy_from_slice(&self.completed_at.to_le_bytes());
+        out
+    }
+
+    /// Decodes and sanity-checks an encoded certificate.
+    pub fn decode(bytes: &[u8]) -> Result<Self, CertificateError> {
+        let bytes: &[u8; CERTIFICATE_ENCODED_LEN] = bytes.try_into().map_err(|_| CertificateError::Malformed)?;
+        if bytes[0] != CERTIFICATE_VERSION {
+            return Err(CertificateError::UnsupportedVersion);
+        }
+
+        let certificate = Self {
+            version: bytes[0],
+            genesis_hash: array(&bytes[1..33]),
+            grant_id: array(&bytes[33..65]),
+            builder: array(&bytes[65..97]),
+            total_grant_amount: u64::from_le_bytes(array(&bytes[97..105])),
+            amount_disbursed: u64::from_le_bytes(array(&bytes[105..113])),
+            final_score: bytes[113],
+            completed_at: u64::from_le_bytes(array(&bytes[114..122])),
+        };
+        // A completed grant has paid out exactly its total.
+        if certificate.amount_disbursed != certificate.total_grant_amount || certificate.final_score > MAX_D_METRIC_SCORE {
+            return Err(CertificateError::Malformed);
+        }
+        Ok(certificate)
+    }
+}
+
+/// Verifies a signed certificate offline: decodes it, checks it was issued on the expected
+/// chain, and checks the issuer's signature over its `attestation_payload`.
+pub fn verify_certificate(
+    encoded: &[u8],
+    signature: &[u8; 64],
+    issuer: &[u8; 32],
+    expected_genesis_hash: &[u8; 32],
+    verifier: &impl SignatureVerifier,
+) -> Result<CompletionCertificate, CertificateError> {
+    let certificate = CompletionCertificate::decode(encoded)?;
+    if certificate.genesis_hash != *expected_genesis_hash {
+        return Err(CertificateError::WrongChain);
+    }
+    let mut message = [0u8; MAX_ATTESTATION_PAYLOAD_LEN];
+    let len = certificate.attestation_payload().encode(&mut message).ok_or(CertificateError::Malformed)?;
+    if !verifier.verify(&message[..len], issuer, signature) {
+        return Err(CertificateError::InvalidSignature);
+    }
+    Ok(certificate)
+}
+
+/// Copies a slice of known length into an array. Callers always pass in-bounds ranges.
+fn array<const N: usize>(bytes: &[u8]) -> [u8; N] {
+    let mut out = [0u8; N];
+    out.copy_from_slice(bytes);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{sign, MockVerifier};
+
+    const GENESIS: [u8; 32] = [7; 32];
+    const ISSUER: [u8; 32] = [9; 32];
+
+    fn sign_certificate(certificate: &CompletionCertificate, key: &[u8; 32]) -> [u8; 64] {
+        let mut message = [0u8; MAX_ATTESTATION_PAYLOAD_LEN];
+        let len = certificate.attestation_payload().encode(&mut message).unwrap();
+        sign(&message[..len], key)
+    }
+
+    fn certificate() -> CompletionCertificate {
+        CompletionCertificate {
+            version: CERTIFICATE_VERSION,
+            genesis_hash: GENESIS,
+            grant_id: [1; 32],
+            builder: [2; 32],
+            total_grant_amount: 3_000_000_000_001,
+            amount_disbursed: 3_000_000_000_001,
+            final_score: 95,
+            completed_at: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    // Test case: A certificate round-trips through its encoding and verifies against the issuer.
+    fn test_round_trip_and_verify() {
+        let encoded = certificate().encode();
+        assert_eq!(encoded[0], CERTIFICATE_VERSION);
+        assert_eq!(encoded[97..105], 3_000_000_000_001u64.to_le_bytes());
+        assert_eq!(CompletionCertificate::decode(&encoded), Ok(certificate()));
+
+        let signature = sign_certificate(&certificate(), &ISSUER);
+        assert_eq!(verify_certificate(&encoded, &signature, &ISSUER, &GENESIS, &MockVerifier), Ok(certificate()));
+    }
+
+    #[test]
+    // Test case: The issuer signs the tagged completion statement, not the raw certificate
+    // bytes, so a signature over the transport encoding does not verify.
+    fn test_signature_is_domain_separated() {
+        let encoded = certificate().encode();
+        let mut message = [0u8; MAX_ATTESTATION_PAYLOAD_LEN];
+        let len = certificate().attestation_payload().encode(&mut message).unwrap();
+        assert_eq!(message[..16], *b"dge:attestation1");
+        assert_eq!(message[48..80], [1; 32]); // grant id
+        assert_eq!(message[80], 2); // Statement::Completion
+
+        let raw = sign(&encoded, &ISSUER);
+        assert_eq!(verify_certificate(&encoded, &raw, &ISSUER, &GENESIS, &MockVerifier), Err(CertificateError::InvalidSignature));
+        assert_eq!(sign(&message[..len], &ISSUER), sign_certificate(&certificate(), &ISSUER));
+    }
+
+    #[test]
+    // Test case: Forged, foreign-chain, malformed, and future-version certificates are rejected.
+    fn test_rejections() {
+        let encoded = certificate().encode();
+        let signature = sign_certificate(&certificate(), &ISSUER);
+
+        let impostor = sign_certificate(&certificate(), &[8; 32]);
+        assert_eq!(verify_certificate(&encoded, &impostor, &ISSUER, &GENESIS, &MockVerifier), Err(CertificateError::InvalidSignature));
+        assert_eq!(verify_certificate(&encoded, &signature, &ISSUER, &[0; 32], &MockVerifier), Err(CertificateError::WrongChain));
+        assert_eq!(verify_certificate(&encoded[1..], &signature, &ISSUER, &GENESIS, &MockVerifier), Err(CertificateError::Malformed));
+
+        let partial = CompletionCertificate { amount_disbursed: 1, ..certificate() }.encode();
+        assert_eq!(CompletionCertificate::decode(&partial), Err(CertificateError::Malformed));
+        let future = CompletionCertificate { version: CERTIFICATE_VERSION + 1, ..certificate() }.encode();
+        assert_eq!(CompletionCertificate::decode(&future), Err(CertificateError::UnsupportedVersion));
+    }
+}
@@ -0,0 +1,197 @@
+// Shared Error Codes
+// A single, stable numeric code for every DGE failure, shared by every chain the engine
+// runs on. Chain-specific error types (the Anchor `DGEError`, the governance module's
+// `ProgramError::Custom` values) map onto these codes, so support tooling can decode
+// a failure the same way regardless of where it was raised.
+//
+// Codes are grouped by domain in blocks of 100. Never renumber or reuse a code;
+// only append new ones.
+
+use crate::attestation::AttestationError;
+use crate::bond::BondError;
+use crate::certificate::CertificateError;
+use crate::evidence::EvidenceError;
+
+macro_rules! dge_error_codes {
+    ($($(#[$meta:meta])* $name:ident = $code:literal => $description:literal,)*) => {
+        /// Stable cross-chain DGE error codes.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        #[repr(u32)]
+        pub enum DgeErrorCode {
+            $($(#[$meta])* $name = $code,)*
+        }
+
+        impl DgeErrorCode {
+            /// Every defined code, in ascending order.
+            pub const ALL: &'static [Self] = &[$(Self::$name,)*];
+
+            /// Decodes a numeric code, e.g. from a failed transaction.
+            pub fn from_code(code: u32) -> Option<Self> {
+                match code {
+                    $($code => Some(Self::$name),)*
+                    _ => None,
+                }
+            }
+
+            /// Human-readable explanation of the failure.
+            pub fn description(self) -> &'static str {
+                match self {
+                    $(Self::$name => $description,)*
+                }
+            }
+        }
+    };
+}
+
+dge_error_codes! {
+    // --- 100-199: Builder Bond and pricing ---
+    PriceUnavailable = 100 => "The price oracle reported a zero or missing price.",
+    ArithmeticOverflow = 101 => "An arithmetic operation overflowed.",
+    InvalidGrantTier = 102 => "The grant tier is not defined in the tier table.",
+    InsufficientBuilderBond = 103 => "The posted Builder Bond is below the requirement for the declared tier.",
+    BondCalculationFailed = 104 => "The Builder Bond could not be computed from the supplied price.",
+    AmountConversion = 105 => "The computed amount does not fit the target integer type.",
+    InvalidTokenDecimals = 106 => "The token's decimal count is not supported.",
+    CollateralDisabled = 107 => "The bond collateral token is not currently accepted.",
+    InvalidCollateralHaircut = 108 => "The collateral haircut is outside the allowed range.",
+    StalePrice = 109 => "The collateral's last oracle price is older than the configured maximum age.",
+    BondLocked = 110 => "The Builder Bond is still locked or has already been settled.",
+
+    // --- 200-299: Grant lifecycle ---
+    GrantLiquidated = 200 => "The grant has been liquidated and paused.",
+    MilestoneOutOfOrder = 201 => "Milestone submission is out of the required sequential order.",
+    // Deprecated: no longer returned; a failing score liquidates the grant instead.
+    DMetricFailed = 202 => "The D-Metric score is below the required threshold.",
+    InvalidBuilderAuthority = 203 => "The new builder authority must be a valid key different from the current one.",
+    GrantFrozen = 204 => "Payouts are frozen while a milestone challenge is open.",
+    MilestoneNotChallengeable = 205 => "Only the most recently paid milestone can be challenged.",
+    ChallengeWindowClosed = 206 => "The challenge window for this milestone has closed.",
+    ChallengeStakeTooLow = 207 => "The challenge stake is below the configured minimum.",
+    GrantNotLiquidated = 208 => "Grant escrow can only be refunded once the grant is liquidated or every tranche is settled.",
+    InvalidMilestoneCount = 209 => "The grant's milestone count is outside the allowed range.",
+    InvalidMilestoneSchedule = 210 => "The milestone schedule is malformed or changes paid milestones.",
+    TrancheOverflow = 211 => "Every milestone tranche of the grant has already been paid.",
+    InvalidGrantTimeline = 212 => "The grant's start time or funding deadline is in the past, or its start is too far out.",
+    GrantNotStarted = 213 => "Milestones cannot be claimed before the grant's start time.",
+    FundingDeadlinePassed = 214 => "The grant's funding deadline has passed.",
+    FundingDeadlineNotReached = 215 => "The grant's funding deadline has not passed yet.",
+    GrantAlreadyFunded = 216 => "The grant has been funded; its bond can no longer be reclaimed.",
+    UnexpectedCpi = 217 => "Payouts and liquidations must be invoked directly, not through another program.",
+    InvalidSubGrant = 218 => "Sub-grants must be funded by a top-level umbrella grant.",
+    SubGrantCapExceeded = 219 => "The sub-grant exceeds what is left of the umbrella grant.",
+    SubGrantNotApproved = 220 => "Sub-grant milestones must be approved by the umbrella grant's builder.",
+    DuplicateFunding = 221 => "A grant can only be funded once.",
+    UmbrellaVaultOpen = 222 => "A sub-grant's escrow goes back to its open umbrella grant.",
+
+    // --- 300-399: Grant registry ---
+    RegistryBucketFull = 300 => "The current registry bucket is full.",
+    RegistryBucketNotNeeded = 301 => "The current registry bucket still has room.",
+
+    // --- 400-499: Metric verifiers ---
+    InsufficientVerifierSignatures = 400 => "Not enough registered verifiers signed the metric submission.",
+    InvalidVerifierThreshold = 401 => "The verifier threshold must be between 1 and the number of verifiers.",
+    VerifierAlreadyRegistered = 402 => "The verifier is already registered.",
+    VerifierNotFound = 403 => "The verifier is not registered.",
+    TooManyVerifiers = 404 => "The verifier set is at maximum capacity.",
+    DuplicateAttester = 405 => "The same attester signed the milestone evidence more than once.",
+    TooManyAttestations = 406 => "The milestone evidence carries more attestations than allowed.",
+    MetricOutOfRange = 407 => "The evidence metric value is outside the configured range.",
+    InvalidEvidenceArtifacts = 408 => "The evidence artifact hashes are missing, too many, or zero.",
+    AttestationChainMismatch = 409 => "The attestation was signed for a different chain.",
+    AttestationExpired = 410 => "The attestation has expired.",
+    InvalidAttestationSignature = 411 => "The attestation signature does not match the attester.",
+
+    // --- 500-599: Governance, configuration, and the insurance fund ---
+    Unauthorized = 500 => "The signer is not the governance authority.",
+    InvalidConfig = 501 => "The configuration parameters are inconsistent.",
+    InsuranceFeeTooHigh = 502 => "The insurance fee exceeds the maximum allowed basis points.",
+    InsufficientInsuranceFunds = 503 => "The insurance fund does not hold enough funds for this withdrawal.",
+    InvalidCoverageAmount = 504 => "Coverage must be non-zero, within the co-funder's contribution, and within the grant's uncovered amount.",
+    CoverageClosed = 505 => "Cover can no longer be bought once a milestone has missed its pass score.",
+    CoverageStillActive = 506 => "Coverage can only expire once its grant is settled.",
+
+    // --- 600-699: Completion certificates ---
+    MalformedCertificate = 600 => "The completion certificate is malformed or internally inconsistent.",
+    UnsupportedCertificateVersion = 601 => "The completion certificate uses an unsupported format version.",
+    CertificateChainMismatch = 602 => "The completion certificate was issued on a different chain.",
+    InvalidCertificateSignature = 603 => "The completion certificate signature does not match the issuer.",
+}
+
+impl DgeErrorCode {
+    /// The numeric code.
+    pub const fn code(self) -> u32 {
+        self as u32
+    }
+}
+
+impl From<BondError> for DgeErrorCode {
+    fn from(error: BondError) -> Self {
+        match error {
+            BondError::ZeroPrice => Self::PriceUnavailable,
+            BondError::Overflow => Self::ArithmeticOverflow,
+            BondError::Conversion => Self::AmountConversion,
+            BondError::InvalidDecimals => Self::InvalidTokenDecimals,
+        }
+    }
+}
+
+impl From<EvidenceError> for DgeErrorCode {
+    fn from(error: EvidenceError) -> Self {
+        match error {
+            EvidenceError::TooFewAttestations => Self::InsufficientVerifierSignatures,
+            EvidenceError::TooManyAttestations => Self::TooManyAttestations,
+            EvidenceError::DuplicateAttester => Self::DuplicateAttester,
+            EvidenceError::MetricOutOfRange => Self::MetricOutOfRange,
+            EvidenceError::InvalidArtifacts => Self::InvalidEvidenceArtifacts,
+        }
+    }
+}
+
+impl From<AttestationError> for DgeErrorCode {
+    fn from(error: AttestationError) -> Self {
+        match error {
+            AttestationError::WrongChain => Self::AttestationChainMismatch,
+            AttestationError::Expired => Self::AttestationExpired,
+            AttestationError::InvalidSignature => Self::InvalidAttestationSignature,
+        }
+    }
+}
+
+impl From<CertificateError> for DgeErrorCode {
+    fn from(error: CertificateError) -> Self {
+        match error {
+            CertificateError::Malformed => Self::MalformedCertificate,
+            CertificateError::UnsupportedVersion => Self::UnsupportedCertificateVersion,
+            CertificateError::WrongChain => Self::CertificateChainMismatch,
+            CertificateError::InvalidSignature => Self::InvalidCertificateSignature,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Test case: Every code decodes back to itself, and codes are unique and ascending.
+    fn test_codes_round_trip() {
+        for pair in DgeErrorCode::ALL.windows(2) {
+            assert!(pair[0].code() < pair[1].code());
+        }
+        for code in DgeErrorCode::ALL {
+            assert_eq!(DgeErrorCode::from_code(code.code()), Some(*code));
+            assert!(!code.description().is_empty());
+        }
+        assert_eq!(DgeErrorCode::from_code(0), None);
+        assert_eq!(DgeErrorCode::from_code(6000), None);
+    }
+
+    #[test]
+    // Test case: Bond errors keep the codes the governance module has always returned.
+    fn test_bond_error_codes_are_stable() {
+        assert_eq!(DgeErrorCode::from(BondError::ZeroPrice).code(), 100);
+        assert_eq!(DgeErrorCode::from(BondError::Overflow).code(), 101);
+        assert_eq!(DgeErrorCode::from(BondError::Conversion).code(), 105);
+        assert_eq!(DgeErrorCode::from(BondError::InvalidDecimals).code(), 106);
+    }
+}
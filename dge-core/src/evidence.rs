@@ -0,0 +1,307 @@
+// Milestone Evidence
+// A standard schema for the evidence behind a milestone claim: the metric snapshot being
+// scored, hashes of the off-chain artifacts backing it (releases, audit reports, dashboards),
+// and the attester signatures vouching for it. Chains validate the structure here at
+// submission instead of storing an opaque hash, so verification can be automated.
+//
+// The wire format is SCALE: fixed-width integers are little-endian, byte arrays are raw, and
+// sequences carry a compact length prefix. Attesters do not sign these bytes: they sign the
+// domain-separated `AttestationPayload` for the evidence's metric statement (see
+// `attestation.rs`), which also binds the chain and the grant. `validate` checks the
+// attestations' count and uniqueness; `verify_attestations` checks the signatures with the
+// chain's scheme (ed25519 on Solana, sr25519 on Substrate).
+
+use crate::attestation::{verify_attestation, AttestationError, AttestationPayload, Statement};
+use crate::certificate::SignatureVerifier;
+
+/// Upper bound on artifact hashes per evidence bundle, keeping submissions small.
+pub const MAX_EVIDENCE_ARTIFACTS: usize = 16;
+
+/// Upper bound on attestations per evidence bundle.
+pub const MAX_EVIDENCE_ATTESTATIONS: usize = 16;
+
+/// The raw metric a milestone is scored on, as observed by the attesters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MetricSnapshot {
+    pub value: u64,
+    /// Unix timestamp (seconds) the value was observed at.
+    pub observed_at: u64,
+}
+
+/// One attester's signature over an `AttestationPayload`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Attestation {
+    pub attester: [u8; 32],
+    pub signature: [u8; 64],
+}
+
+/// The evidence submitted with a milestone claim.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Evidence<'a> {
+    /// 1-based milestone the evidence is for.
+    pub milestone_index: u8,
+    pub metric: MetricSnapshot,
+    /// 32-byte hashes (e.g. SHA-256 or BLAKE2b-256) of the supporting artifacts.
+    pub artifact_hashes: &'a [[u8; 32]],
+    /// Unix timestamp (seconds) after which the attestations are no longer accepted.
+    pub expires_at: u64,
+    pub attestations: &'a [Attestation],
+}
+
+/// Acceptance rules for submitted evidence. Chains source these from their governance config.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EvidenceRules {
+    /// Distinct attesters required.
+    pub min_attestations: u8,
+    /// Inclusive range of plausible metric values; anything outside is rejected outright.
+    pub min_metric: u64,
+    pub max_metric: u64,
+}
+
+/// Reasons evidence is rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EvidenceError {
+    /// Fewer distinct attesters than `min_attestations`.
+    TooFewAttestations,
+    /// More attestations than `MAX_EVIDENCE_ATTESTATIONS`.
+    TooManyAttestations,
+    /// The same attester appears more than once.
+    DuplicateAttester,
+    /// The metric value is outside the configured range.
+    MetricOutOfRange,
+    /// No artifact hashes, more than `MAX_EVIDENCE_ARTIFACTS`, or an all-zero hash.
+    InvalidArtifacts,
+}
+
+impl<'a> Evidence<'a> {
+    /// Structural validation: artifact and attestation counts, attester uniqueness, and the
+    /// metric range. Signatures must still be verified by the caller.
+    pub fn validate(&self, rules: &EvidenceRules) -> Result<(), EvidenceError> {
+        if self.artifact_hashes.is_empty()
+            || self.artifact_hashes.len() > MAX_EVIDENCE_ARTIFACTS
+            || self.artifact_hashes.contains(&[0u8; 32])
+        {
+            return Err(EvidenceError::InvalidArtifacts);
+        }
+        if self.metric.value < rules.min_metric || self.metric.value > rules.max_metric {
+            return Err(EvidenceError::MetricOutOfRange);
+        }
+        if self.attestations.len() > MAX_EVIDENCE_ATTESTATIONS {
+            return Err(EvidenceError::TooManyAttestations);
+        }
+        for (i, attestation) in self.attestations.iter().enumerate() {
+            if self.attestations[i + 1..].iter().any(|other| other.attester == attestation.attester) {
+                return Err(EvidenceError::DuplicateAttester);
+            }
+        }
+        if self.attestations.len() < rules.min_attestations as usize {
+            return Err(EvidenceError::TooFewAttestations);
+        }
+        Ok(())
+    }
+
+    /// The payload every attester signs for this evidence on the chain with `genesis_hash`,
+    /// for the grant `grant` (the Grant PDA on Solana).
+    pub fn attestation_payload(&self, genesis_hash: [u8; 32], grant: [u8; 32]) -> AttestationPayload<'a> {
+        AttestationPayload {
+            genesis_hash,
+            subject: grant,
+            statement: Statement::Metric {
+                milestone_index: self.milestone_index,
+                metric: self.metric,
+                artifact_hashes: self.artifact_hashes,
+            },
+            expires_at: self.expires_at,
+        }
+    }
+
+    /// Checks every attester's signature over `attestation_payload(genesis_hash, grant)` at
+    /// `now` (Unix seconds). Run `validate` first for the count and uniqueness rules.
+    pub fn verify_attestations(
+        &self,
+        genesis_hash: [u8; 32],
+        grant: [u8; 32],
+        now: u64,
+        verifier: &impl SignatureVerifier,
+    ) -> Result<(), AttestationError> {
+        let payload = self.attestation_payload(genesis_hash, grant);
+        for attestation in self.attestations {
+            verify_attestation(&payload, attestation, &genesis_hash, now, verifier)?;
+        }
+        Ok(())
+    }
+
+    /// Size of `encode`'s output, or `None` if a length is too large to encode.
+    pub fn encoded_len(&self) -> Option<usize> {
+        let artifacts = compact_len(self.artifact_hashes.len())? + 32 * self.artifact_hashes.len();
+        let attestations = compact_len(self.attestations.len())? + 96 * self.attestations.len();
+        Some(1 + 8 + 8 + artifacts + 8 + attestations)
+    }
+
+    /// SCALE-encodes the whole evidence bundle into `out`.
+    /// Returns the bytes written, or `None` if `out` is too small.
+    pub fn encode(&self, out: &mut [u8]) -> Option<usize> {
+        let mut writer = Writer { out, position: 0 };
+        writer.write(&[self.milestone_index])?;
+        writer.write(&self.metric.value.to_le_bytes())?;
+        writer.write(&self.metric.observed_at.to_le_bytes())?;
+        writer.write_compact_len(self.artifact_hashes.len())?;
+        for hash in self.artifact_hashes {
+            writer.write(hash)?;
+        }
+        writer.write(&self.expires_at.to_le_bytes())?;
+        writer.write_compact_len(self.attestations.len())?;
+        for attestation in self.attestations {
+            writer.write(&attestation.attester)?;
+            writer.write(&attestation.signature)?;
+        }
+        Some(writer.position)
+    }
+}
+
+/// Bounds-checked cursor over an output buffer.
+pub(crate) struct Writer<'a> {
+    pub(crate) out: &'a mut [u8],
+    pub(crate) position: usize,
+}
+
+impl Writer<'_> {
+    pub(crate) fn write(&mut self, bytes: &[u8]) -> Option<()> {
+        let end = self.position.checked_add(bytes.len())?;
+        self.out.get_mut(self.position..end)?.copy_from_slice(bytes);
+        self.position = end;
+        Some(())
+    }
+
+    /// SCALE compact encoding of a sequence length (single-, two-, and four-byte modes).
+    pub(crate) fn write_compact_len(&mut self, len: usize) -> Option<()> {
+        let value = u32::try_from(len).ok()?;
+        match compact_len(len)? {
+            1 => self.write(&[(value << 2) as u8]),
+            2 => self.write(&(((value << 2) | 0b01) as u16).to_le_bytes()),
+            _ => self.write(&((value << 2) | 0b10).to_le_bytes()),
+        }
+    }
+}
+
+/// Bytes taken by the compact length prefix. Lengths of 2^30 and above are not supported.
+pub(crate) fn compact_len(len: usize) -> Option<usize> {
+    match len {
+        0..=0x3f => Some(1),
+        0x40..=0x3fff => Some(2),
+        0x4000..=0x3fff_ffff => Some(4),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::{ATTESTATION_DOMAIN_TAG, MAX_ATTESTATION_PAYLOAD_LEN};
+
+    const RULES: EvidenceRules = EvidenceRules { min_attestations: 2, min_metric: 0, max_metric: 1_000_000 };
+    const ARTIFACTS: [[u8; 32]; 2] = [[0xaa; 32], [0xbb; 32]];
+
+    fn attestation(attester: u8) -> Attestation {
+        Attestation { attester: [attester; 32], signature: [attester; 64] }
+    }
+
+    fn evidence<'a>(artifact_hashes: &'a [[u8; 32]], attestations: &'a [Attestation]) -> Evidence<'a> {
+        Evidence {
+            milestone_index: 1,
+            metric: MetricSnapshot { value: 1_000, observed_at: 1_700_000_000 },
+            artifact_hashes,
+            expires_at: 1_700_000_600,
+            attestations,
+        }
+    }
+
+    #[test]
+    // Test case: Well-formed evidence passes; each structural defect maps to its error.
+    fn test_validate() {
+        let attestations = [attestation(1), attestation(2)];
+        assert_eq!(evidence(&ARTIFACTS, &attestations).validate(&RULES), Ok(()));
+
+        assert_eq!(evidence(&ARTIFACTS, &attestations[..1]).validate(&RULES), Err(EvidenceError::TooFewAttestations));
+        let duplicated = [attestation(1), attestation(1)];
+        assert_eq!(evidence(&ARTIFACTS, &duplicated).validate(&RULES), Err(EvidenceError::DuplicateAttester));
+        let crowded = [attestation(1); MAX_EVIDENCE_ATTESTATIONS + 1];
+        assert_eq!(evidence(&ARTIFACTS, &crowded).validate(&RULES), Err(EvidenceError::TooManyAttestations));
+
+        assert_eq!(evidence(&[], &attestations).validate(&RULES), Err(EvidenceError::InvalidArtifacts));
+        assert_eq!(evidence(&[[0; 32]], &attestations).validate(&RULES), Err(EvidenceError::InvalidArtifacts));
+        let narrow = EvidenceRules { max_metric: 999, ..RULES };
+        assert_eq!(evidence(&ARTIFACTS, &attestations).validate(&narrow), Err(EvidenceError::MetricOutOfRange));
+    }
+
+    #[test]
+    // Test case: The encoding matches SCALE byte for byte.
+    fn test_scale_encoding() {
+        let attestations = [attestation(1)];
+        let evidence = evidence(&ARTIFACTS[..1], &attestations);
+        let mut buffer = [0u8; 256];
+
+        let len = evidence.encode(&mut buffer).unwrap();
+        assert_eq!(Some(len), evidence.encoded_len());
+        assert_eq!(len, 1 + 8 + 8 + 1 + 32 + 8 + 1 + 96);
+        assert_eq!(buffer[0], 1);
+        assert_eq!(buffer[1..9], 1_000u64.to_le_bytes());
+        assert_eq!(buffer[9..17], 1_700_000_000u64.to_le_bytes());
+        assert_eq!(buffer[17], 1 << 2); // compact(1)
+        assert_eq!(buffer[18..50], [0xaa; 32]);
+        assert_eq!(buffer[50..58], 1_700_000_600u64.to_le_bytes());
+        assert_eq!(buffer[58], 1 << 2);
+        assert_eq!(buffer[59..91], [1; 32]);
+        assert_eq!(buffer[91..155], [1; 64]);
+
+        // Too small a buffer fails cleanly.
+        assert_eq!(evidence.encode(&mut buffer[..len - 1]), None);
+    }
+
+    #[test]
+    // Test case: Attesters sign the tagged metric statement for one chain and one grant, so
+    // the same signatures fail for another grant.
+    fn test_signing_payload_binds_grant() {
+        let attestations = [attestation(1)];
+        let evidence = evidence(&ARTIFACTS, &attestations);
+        let payload = evidence.attestation_payload([7; 32], [3; 32]);
+        let mut buffer = [0u8; MAX_ATTESTATION_PAYLOAD_LEN];
+        payload.encode(&mut buffer).unwrap();
+        assert_eq!(buffer[..16], ATTESTATION_DOMAIN_TAG);
+        assert_eq!(buffer[48..80], [3; 32]);
+        assert_eq!(buffer[81], 1); // milestone index
+
+        // Stand-in scheme: a valid "signature" repeats the payload's grant-bound bytes.
+        struct PrefixVerifier;
+        impl SignatureVerifier for PrefixVerifier {
+            fn verify(&self, message: &[u8], _: &[u8; 32], signature: &[u8; 64]) -> bool {
+                message[16..80] == signature[..]
+            }
+        }
+        let mut signature = [0u8; 64];
+        signature.copy_from_slice(&buffer[16..80]);
+        let signed = [Attestation { attester: [1; 32], signature }];
+        let evidence = Evidence { attestations: &signed, ..evidence };
+        assert_eq!(evidence.verify_attestations([7; 32], [3; 32], 1_700_000_000, &PrefixVerifier), Ok(()));
+        assert_eq!(
+            evidence.verify_attestations([7; 32], [4; 32], 1_700_000_000, &PrefixVerifier),
+            Err(AttestationError::InvalidSignature)
+        );
+        assert_eq!(
+            evidence.verify_attestations([7; 32], [3; 32], 1_700_000_601, &PrefixVerifier),
+            Err(AttestationError::Expired)
+        );
+    }
+
+    #[test]
+    // Test case: Compact length prefixes switch modes at 2^6 and 2^14.
+    fn test_compact_lengths() {
+        let mut buffer = [0u8; 4];
+        for (len, expected) in [(63usize, &[0xfc][..]), (64, &[0x01, 0x01]), (16_384, &[0x02, 0x00, 0x01, 0x00])] {
+            let mut writer = Writer { out: &mut buffer, position: 0 };
+            writer.write_compact_len(len).unwrap();
+            assert_eq!(&writer.out[..writer.position], expected);
+        }
+        assert_eq!(compact_len(1 << 30), None);
+    }
+}
@@ -0,0 +1,185 @@
+// Fixed-point conventions shared across the DGE.
+// Floating-point numbers are prohibited on-chain, so every fractional value is an
+// integer scaled by 10^18 (the common fixed-point scale).
+//
+// All multiplication and division goes through `mul_div`, which keeps a full 256-bit
+// intermediate product and applies an explicit `Rounding` mode exactly once, so every
+// chain that links this crate rounds identically.
+
+/// Scale factor for fixed-point math. A value of 1_000_000_000_000_000_000 represents 1.0.
+pub const SCALE: u128 = 1_000_000_000_000_000_000;
+
+/// How a division remainder is resolved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    /// Round toward zero (truncate).
+    Floor,
+    /// Round up whenever there is a non-zero remainder.
+    Ceil,
+    /// Round to the nearest value; exact halves round up.
+    Nearest,
+}
+
+/// An unsigned fixed-point number with 18 decimal places, stored as `value * SCALE`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UFixed18(u128);
+
+impl UFixed18 {
+    /// 0.0
+    pub const ZERO: Self = Self(0);
+    /// 1.0
+    pub const ONE: Self = Self(SCALE);
+
+    /// Wraps an already-scaled value (e.g., `1_500_000_000_000_000_000` for 1.5).
+    pub const fn from_raw(raw: u128) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the underlying scaled value.
+    pub const fn raw(self) -> u128 {
+        self.0
+    }
+
+    /// Converts a whole number into fixed-point, failing if it exceeds the representable range.
+    pub fn from_integer(value: u128) -> Option<Self> {
+        value.checked_mul(SCALE).map(Self)
+    }
+
+    /// Converts back to a whole number using the given rounding mode.
+    pub fn to_integer(self, rounding: Rounding) -> u128 {
+        // Dividing by SCALE can never overflow, so the fallback is unreachable.
+        mul_div(self.0, 1, SCALE, rounding).unwrap_or(u128::MAX)
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    /// `self * rhs`, rounded as requested. Returns `None` on overflow.
+    pub fn checked_mul(self, rhs: Self, rounding: Rounding) -> Option<Self> {
+        mul_div(self.0, rhs.0, SCALE, rounding).map(Self)
+    }
+
+    /// `self / rhs`, rounded as requested. Returns `None` on overflow or division by zero.
+    pub fn checked_div(self, rhs: Self, rounding: Rounding) -> Option<Self> {
+        mul_div(self.0, SCALE, rhs.0, rounding).map(Self)
+    }
+}
+
+/// Computes `a * b / denominator` with a 256-bit intermediate product and a single rounding step.
+/// Returns `None` if `denominator` is zero or the result does not fit in a u128.
+pub fn mul_div(a: u128, b: u128, denominator: u128, rounding: Rounding) -> Option<u128> {
+    if denominator == 0 {
+        return None;
+    }
+
+    let (high, low) = widening_mul(a, b);
+    let (quotient, remainder) = div_wide(high, low, denominator)?;
+
+    let round_up = match rounding {
+        Rounding::Floor => false,
+        Rounding::Ceil => remainder != 0,
+        // remainder >= denominator / 2, written so it cannot overflow.
+        Rounding::Nearest => remainder != 0 && remainder >= denominator - remainder,
+    };
+
+    if round_up {
+        quotient.checked_add(1)
+    } else {
+        Some(quotient)
+    }
+}
+
+/// Full 128x128 -> 256-bit multiplication, returned as (high, low) halves.
+fn widening_mul(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+
+    let (a_hi, a_lo) = (a >> 64, a & MASK);
+    let (b_hi, b_lo) = (b >> 64, b & MASK);
+
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+
+    let middle = (lo_lo >> 64) + (hi_lo & MASK) + (lo_hi & MASK);
+    let low = (lo_lo & MASK) | (middle << 64);
+    let high = hi_hi + (hi_lo >> 64) + (lo_hi >> 64) + (middle >> 64);
+
+    (high, low)
+}
+
+/// Divides the 256-bit value (high, low) by `denominator` using shift-subtract long division.
+/// Returns (quotient, remainder), or `None` if the quotient does not fit in a u128.
+fn div_wide(high: u128, low: u128, denominator: u128) -> Option<(u128, u128)> {
+    if high == 0 {
+        return Some((low / denominator, low % denominator));
+    }
+    if high >= denominator {
+        return None;
+    }
+
+    let mut remainder = high;
+    let mut quotient: u128 = 0;
+    for bit in (0..128).rev() {
+        // Shift the next dividend bit in; `carry` holds the bit pushed past 2^128.
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((low >> bit) & 1);
+        quotient <<= 1;
+        if carry == 1 || remainder >= denominator {
+            remainder = remainder.wrapping_sub(denominator);
+            quotient |= 1;
+        }
+    }
+
+    Some((quotient, remainder))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Test case: 10 / 4 = 2.5 under each rounding mode.
+    fn test_rounding_modes() {
+        assert_eq!(mul_div(10, 1, 4, Rounding::Floor), Some(2));
+        assert_eq!(mul_div(10, 1, 4, Rounding::Ceil), Some(3));
+        assert_eq!(mul_div(10, 1, 4, Rounding::Nearest), Some(3));
+        assert_eq!(mul_div(9, 1, 4, Rounding::Nearest), Some(2));
+        assert_eq!(mul_div(8, 1, 4, Rounding::Ceil), Some(2));
+    }
+
+    #[test]
+    // Test case: Intermediate products above u128::MAX still divide exactly.
+    fn test_wide_intermediate() {
+        let seven_thousand_five_hundred = UFixed18::from_integer(7_500).unwrap();
+        let price = UFixed18::from_raw(SCALE / 2); // 0.5
+        let amount = seven_thousand_five_hundred.checked_div(price, Rounding::Floor).unwrap();
+        assert_eq!(amount.to_integer(Rounding::Floor), 15_000);
+
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX, Rounding::Floor), Some(u128::MAX));
+    }
+
+    #[test]
+    // Test case: Overflow and division by zero are reported rather than wrapped.
+    fn test_checked_failures() {
+        assert_eq!(mul_div(u128::MAX, 2, 1, Rounding::Floor), None);
+        assert_eq!(mul_div(1, 1, 0, Rounding::Floor), None);
+        assert_eq!(UFixed18::ONE.checked_div(UFixed18::ZERO, Rounding::Floor), None);
+        assert_eq!(mul_div(u128::MAX, 1, 1, Rounding::Ceil), Some(u128::MAX));
+    }
+
+    #[test]
+    // Test case: 1/3 rounds down, up, and to nearest as documented.
+    fn test_fixed_division() {
+        let one_third_floor = UFixed18::ONE.checked_div(UFixed18::from_raw(3 * SCALE), Rounding::Floor).unwrap();
+        let one_third_ceil = UFixed18::ONE.checked_div(UFixed18::from_raw(3 * SCALE), Rounding::Ceil).unwrap();
+        assert_eq!(one_third_floor.raw(), 333_333_333_333_333_333);
+        assert_eq!(one_third_ceil.raw(), 333_333_333_333_333_334);
+        assert_eq!(UFixed18::from_raw(2_500_000_000_000_000_000).to_integer(Rounding::Nearest), 3);
+    }
+}
@@ -0,0 +1,27 @@
+//! Depth Grant Engine (DGE) Core
+//! Chain-agnostic math shared by every DGE deployment: the fixed-point helpers,
+//! the Adaptive Quorum curve, the Builder Bond sizing, the D-Metric scoring, the milestone
+//! evidence schema, the signed attestation format, tranche payouts, grant completion
+//! certificates, the quadratic matching math, and the error codes every chain reports.
+//!
+//! Everything here is `no_std`-compatible, allocation-free, and purely deterministic, so the
+//! Solana program and the governance module produce bit-identical results. The default `std`
+//! feature only links the standard library for the cdylib; the optional `wasm` feature adds
+//! JavaScript bindings for frontends.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod attestation;
+pub mod bond;
+pub mod certificate;
+pub mod error;
+pub mod evidence;
+pub mod fixed_point;
+pub mod matching;
+pub mod payout;
+pub mod quorum;
+pub mod scoring;
+#[cfg(test)]
+mod test_support;
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -0,0 +1,229 @@
+// Quadratic Matching (QF)
+// Sizes the matching subsidy a project earns from a round's community contributions,
+// with two anti-sybil defenses:
+// * Contribution caps: each contributor's matchable amount grows with their D-Metric
+//   and account age, so freshly minted accounts cannot farm the matching pool.
+// * Pairwise coordination discounting (Gitcoin-style): the subsidy a pair of contributors
+//   generates shrinks as the pair co-funds more of the same projects.
+//
+// All amounts are token base units. Matches always round down so the pool is never overdrawn.
+
+use crate::fixed_point::{mul_div, Rounding};
+use crate::scoring::MAX_D_METRIC_SCORE;
+
+/// Governance parameters for the per-contributor matching cap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ContributionCapParams {
+    /// Cap for a contributor with a perfect D-Metric and a fully matured account.
+    pub max_matchable: u64,
+    /// Account age, in days, at which the age factor reaches 100%.
+    pub maturity_days: u32,
+}
+
+/// One contributor's (already aggregated) contribution to one project.
+/// Contributors must appear at most once per project.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Contribution {
+    pub contributor: u32,
+    pub project: u32,
+    pub amount: u64,
+    /// The contributor's `contribution_cap`; only `min(amount, cap)` is matched.
+    pub cap: u64,
+}
+
+impl Contribution {
+    /// The part of the contribution that counts toward matching.
+    pub fn matchable(&self) -> u64 {
+        self.amount.min(self.cap)
+    }
+}
+
+/// The largest amount of a contributor's funding that counts toward matching:
+/// `max_matchable * (d_metric / 100) * min(age / maturity, 1)`, rounded down.
+pub fn contribution_cap(d_metric: u8, account_age_days: u32, params: &ContributionCapParams) -> u64 {
+    let d_metric = d_metric.min(MAX_D_METRIC_SCORE) as u128;
+    // A zero maturity disables the age factor.
+    let (age, maturity) = match params.maturity_days {
+        0 => (1, 1),
+        maturity_days => (account_age_days.min(maturity_days) as u128, maturity_days as u128),
+    };
+
+    // Both factors are at most 1, so the result never exceeds `max_matchable` (and the
+    // fallback is unreachable).
+    let denominator = MAX_D_METRIC_SCORE as u128 * maturity;
+    mul_div(params.max_matchable as u128, d_metric * age, denominator, Rounding::Floor).unwrap_or(0) as u64
+}
+
+/// Classic quadratic funding subsidy for `project`: `(Σ √cᵢ)² − Σ cᵢ`, over each
+/// contribution's capped, matchable amount. Returns `None` if the intermediate sums overflow.
+pub fn quadratic_match(contributions: &[Contribution], project: u32) -> Option<u128> {
+    // Expanding the square, the subsidy is exactly 2 · Σ_{i<j} √(cᵢ·cⱼ); summing the pair
+    // terms keeps every square root on an integer product instead of squaring rounded roots.
+    let mut subsidy: u128 = 0;
+    for (i, a) in contributions.iter().enumerate().filter(|(_, c)| c.project == project) {
+        for b in contributions[i + 1..].iter().filter(|c| c.project == project) {
+            subsidy = subsidy.checked_add(pair_root(a.matchable(), b.matchable()))?;
+        }
+    }
+    subsidy.checked_mul(2)
+}
+
+/// Coordination between two contributors across the whole round: `k = Σ_p √(c_a,p · c_b,p)`.
+/// High values mean the pair repeatedly funds the same projects.
+pub fn pairwise_coordination(contributions: &[Contribution], a: u32, b: u32) -> Option<u128> {
+    let mut coordination: u128 = 0;
+    for from_a in contributions.iter().filter(|c| c.contributor == a) {
+        for from_b in contributions.iter().filter(|c| c.contributor == b && c.project == from_a.project) {
+            coordination = coordination.checked_add(pair_root(from_a.matchable(), from_b.matchable()))?;
+        }
+    }
+    Some(coordination)
+}
+
+/// Pairwise-bounded matching subsidy for `project`:
+/// `2 · Σ_{i<j} √(cᵢ·cⱼ) · M / (M + kᵢⱼ)`, where `kᵢⱼ` is the pair's round-wide coordination.
+///
+/// `coordination_bound` (M) sets how quickly coordinated pairs are discounted: a pair whose
+/// coordination equals M contributes half of its classic QF subsidy. As M grows the result
+/// approaches `quadratic_match`. Amounts are capped as in `quadratic_match`. Runs in O(n³)
+/// over `contributions`; intended for round settlement, not per-contribution updates.
+pub fn pairwise_match(contributions: &[Contribution], project: u32, coordination_bound: u128) -> Option<u128> {
+    let mut subsidy: u128 = 0;
+    for (i, a) in contributions.iter().enumerate().filter(|(_, c)| c.project == project) {
+        for b in contributions[i + 1..].iter().filter(|c| c.project == project) {
+            let coordination = pairwise_coordination(contributions, a.contributor, b.contributor)?;
+            let denominator = coordination_bound.checked_add(coordination)?;
+            // M = k = 0 only when one side has nothing matchable, so the pair adds nothing.
+            if denominator == 0 {
+                continue;
+            }
+            let term = mul_div(pair_root(a.matchable(), b.matchable()), coordination_bound, denominator, Rounding::Floor)?;
+            subsidy = subsidy.checked_add(term)?;
+        }
+    }
+    subsidy.checked_mul(2)
+}
+
+/// `⌊√(a·b)⌋`. The product of two u64 values always fits in a u128.
+fn pair_root(a: u64, b: u64) -> u128 {
+    isqrt(a as u128 * b as u128)
+}
+
+/// Integer square root (floor) by Newton's method.
+fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    // Start from a power of two at or above the root; each step strictly decreases until converged.
+    let mut estimate = 1u128 << (128 - value.leading_zeros()).div_ceil(2);
+    loop {
+        let next = (estimate + value / estimate) / 2;
+        if next >= estimate {
+            return estimate;
+        }
+        estimate = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: ContributionCapParams = ContributionCapParams { max_matchable: 1_000_000, maturity_days: 180 };
+
+    fn contribution(contributor: u32, project: u32, amount: u64) -> Contribution {
+        Contribution { contributor, project, amount, cap: u64::MAX }
+    }
+
+    fn round(amounts: &[u64]) -> [Contribution; 4] {
+        let mut round = [contribution(0, 7, 0); 4];
+        for (i, amount) in amounts.iter().enumerate() {
+            round[i] = contribution(i as u32 + 1, 7, *amount);
+        }
+        round
+    }
+
+    #[test]
+    // Test case: The cap scales with both the D-Metric and the account age, and saturates.
+    fn test_contribution_cap() {
+        assert_eq!(contribution_cap(100, 180, &PARAMS), 1_000_000);
+        assert_eq!(contribution_cap(100, 1_000, &PARAMS), 1_000_000);
+        assert_eq!(contribution_cap(50, 90, &PARAMS), 250_000);
+        assert_eq!(contribution_cap(80, 0, &PARAMS), 0);
+        assert_eq!(contribution_cap(0, 180, &PARAMS), 0);
+    }
+
+    #[test]
+    // Test case: Known QF vectors. Four contributions of 1e6 earn (4·1e3)² − 4e6 = 12e6;
+    // 4 and 9 earn (2 + 3)² − 13 = 12. A lone contributor earns nothing.
+    fn test_quadratic_match_vectors() {
+        assert_eq!(quadratic_match(&round(&[1_000_000; 4]), 7), Some(12_000_000));
+        assert_eq!(quadratic_match(&round(&[4, 9]), 7), Some(12));
+        assert_eq!(quadratic_match(&round(&[1_000_000]), 7), Some(0));
+        assert_eq!(quadratic_match(&[], 7), Some(0));
+    }
+
+    #[test]
+    // Test case: Amounts above a contributor's cap are matched at the cap: 4 and 100 capped
+    // at 9 earn the same as 4 and 9.
+    fn test_caps_apply_inside_matching() {
+        let capped = [contribution(1, 7, 4), Contribution { cap: 9, ..contribution(2, 7, 100) }];
+        assert_eq!(quadratic_match(&capped, 7), Some(12));
+        assert_eq!(pairwise_coordination(&capped, 1, 2), Some(6));
+        // 2 · 6 · 6 / (6 + 6)
+        assert_eq!(pairwise_match(&capped, 7, 6), Some(6));
+        let zero_cap = [contribution(1, 7, 4), Contribution { cap: 0, ..contribution(2, 7, 100) }];
+        assert_eq!(quadratic_match(&zero_cap, 7), Some(0));
+    }
+
+    #[test]
+    // Test case: With M = 0 and a zero contribution the pair's denominator is zero; the
+    // match is 0 rather than a failure.
+    fn test_pairwise_match_degenerate_bound() {
+        let round = [contribution(1, 7, 0), contribution(2, 7, 1_000)];
+        assert_eq!(pairwise_match(&round, 7, 0), Some(0));
+        assert_eq!(pairwise_match(&[contribution(1, 7, 1_000), contribution(2, 7, 1_000)], 7, 0), Some(0));
+    }
+
+    #[test]
+    // Test case: Four independent contributors of 1e6 each have k = 1e6 per pair.
+    // With M = 1e6 every pair keeps half its subsidy (6e6); with a huge M it matches QF.
+    fn test_pairwise_match_vectors() {
+        let round = [
+            contribution(1, 7, 1_000_000),
+            contribution(2, 7, 1_000_000),
+            contribution(3, 7, 1_000_000),
+            contribution(4, 7, 1_000_000),
+        ];
+        assert_eq!(pairwise_match(&round, 7, 1_000_000), Some(6_000_000));
+        assert_eq!(pairwise_match(&round, 7, 1_000_000_000_000_000), Some(11_999_988));
+        assert_eq!(pairwise_match(&round, 8, 1_000_000), Some(0));
+    }
+
+    #[test]
+    // Test case: A pair that also co-funds a second project is discounted harder than an
+    // independent pair with identical contributions to the target project.
+    fn test_pairwise_coordination_discount() {
+        let independent = [contribution(1, 7, 10_000), contribution(2, 7, 10_000)];
+        let colluding = [
+            contribution(1, 7, 10_000),
+            contribution(2, 7, 10_000),
+            contribution(1, 8, 10_000),
+            contribution(2, 8, 10_000),
+        ];
+        assert_eq!(pairwise_coordination(&colluding, 1, 2), Some(20_000));
+        // 2 · 10_000 · 10_000 / (10_000 + k)
+        assert_eq!(pairwise_match(&independent, 7, 10_000), Some(10_000));
+        assert_eq!(pairwise_match(&colluding, 7, 10_000), Some(6_666));
+    }
+
+    #[test]
+    // Test case: Integer square roots are exact floors, including at the u128 extremes.
+    fn test_isqrt() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(u64::MAX as u128 * u64::MAX as u128), u64::MAX as u128);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+    }
+}
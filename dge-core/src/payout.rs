@@ -0,0 +1,109 @@
+// Tranche Payouts
+// Splits a grant into equal milestone tranches. Integer division leaves a remainder
+// whenever the total does not divide evenly, so the final tranche pays whatever is
+// left instead of the nominal amount: the builder always receives the full grant,
+// and nothing is stranded in escrow.
+//
+// Deployments may also pay partial tranches: a milestone scoring between a floor and its
+// target score releases a proportional share, and the shortfall stays with the funder.
+
+use crate::fixed_point::{mul_div, Rounding};
+
+/// The amount owed for milestone `milestone_index` (1-based) of a grant.
+///
+/// Every tranche but the last pays `total_grant_amount / total_tranches`; the last pays
+/// `total_grant_amount - amount_disbursed`. Returns `None` for a zero tranche count, an
+/// out-of-range milestone, or a disbursed amount above the total.
+pub fn tranche_amount(total_grant_amount: u64, total_tranches: u8, milestone_index: u8, amount_disbursed: u64) -> Option<u64> {
+    if total_tranches == 0 || milestone_index == 0 || milestone_index > total_tranches {
+        return None;
+    }
+
+    let remaining = total_grant_amount.checked_sub(amount_disbursed)?;
+    if milestone_index == total_tranches {
+        Some(remaining)
+    } else {
+        Some((total_grant_amount / total_tranches as u64).min(remaining))
+    }
+}
+
+/// The share of `tranche` released for a milestone scored `score`:
+/// `tranche × clamp((score - floor) / (target - floor), 0, 1)`, rounded down.
+///
+/// Scores at or above `target` release the full tranche and scores at or below `floor` release
+/// nothing; with `floor == target` this is the binary pass/fail rule. Returns `None` if
+/// `floor` exceeds `target`.
+pub fn proportional_tranche_amount(tranche: u64, score: u8, floor: u8, target: u8) -> Option<u64> {
+    if floor > target {
+        return None;
+    }
+    if score >= target {
+        return Some(tranche);
+    }
+    if score <= floor {
+        return Some(0);
+    }
+
+    let released = mul_div(tranche as u128, (score - floor) as u128, (target - floor) as u128, Rounding::Floor)?;
+    u64::try_from(released).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pays every milestone in order and returns the individual tranches.
+    fn pay_all(total: u64, tranches: u8) -> [u64; 16] {
+        let mut paid = [0u64; 16];
+        let mut disbursed = 0;
+        for milestone in 1..=tranches {
+            let amount = tranche_amount(total, tranches, milestone, disbursed).unwrap();
+            paid[milestone as usize - 1] = amount;
+            disbursed += amount;
+        }
+        assert_eq!(disbursed, total);
+        paid
+    }
+
+    #[test]
+    // Test case: 10 over 3 tranches pays 3, 3, then the remaining 4.
+    fn test_odd_division_final_tranche_sweeps_remainder() {
+        assert_eq!(pay_all(10, 3)[..3], [3, 3, 4]);
+        assert_eq!(pay_all(1_000_000_001, 2)[..2], [500_000_000, 500_000_001]);
+        assert_eq!(pay_all(u64::MAX, 16)[15], u64::MAX - 15 * (u64::MAX / 16));
+    }
+
+    #[test]
+    // Test case: Even divisions and single-tranche grants are unaffected.
+    fn test_even_and_single_tranche() {
+        assert_eq!(pay_all(9, 3)[..3], [3, 3, 3]);
+        assert_eq!(pay_all(7, 1)[..1], [7]);
+        // Fewer base units than tranches: everything lands in the final tranche.
+        assert_eq!(pay_all(2, 3)[..3], [0, 0, 2]);
+    }
+
+    #[test]
+    // Test case: With a floor of 50 and a target of 75, a score of 60 releases 40% of the tranche.
+    fn test_proportional_tranche() {
+        assert_eq!(proportional_tranche_amount(1_000, 60, 50, 75), Some(400));
+        assert_eq!(proportional_tranche_amount(1_000, 74, 50, 75), Some(960));
+        assert_eq!(proportional_tranche_amount(1_000, 80, 50, 75), Some(1_000));
+        assert_eq!(proportional_tranche_amount(1_000, 50, 50, 75), Some(0));
+        // Fractions round down, in the funder's favor.
+        assert_eq!(proportional_tranche_amount(10, 51, 50, 53), Some(3));
+        assert_eq!(proportional_tranche_amount(u64::MAX, 99, 0, 100), Some((u64::MAX as u128 * 99 / 100) as u64));
+        // A floor equal to the target is plain pass/fail.
+        assert_eq!(proportional_tranche_amount(1_000, 75, 75, 75), Some(1_000));
+        assert_eq!(proportional_tranche_amount(1_000, 74, 75, 75), Some(0));
+        assert_eq!(proportional_tranche_amount(1_000, 80, 76, 75), None);
+    }
+
+    #[test]
+    // Test case: Malformed inputs are rejected instead of panicking or wrapping.
+    fn test_invalid_inputs() {
+        assert_eq!(tranche_amount(10, 0, 1, 0), None);
+        assert_eq!(tranche_amount(10, 3, 0, 0), None);
+        assert_eq!(tranche_amount(10, 3, 4, 0), None);
+        assert_eq!(tranche_amount(10, 3, 3, 11), None);
+    }
+}
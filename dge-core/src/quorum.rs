@@ -0,0 +1,84 @@
+// Adaptive Quorum (AQ)
+// Dynamically adjusts the required governance quorum percentage based on
+// the protocol's total Depth Score (a measure of TVL/staking).
+
+use crate::fixed_point::{Rounding, UFixed18};
+
+// --- Adaptive Quorum (AQ) Constants ---
+
+/// The lowest possible Quorum percentage (Floor), represented as a scaled value.
+pub const QUORUM_FLOOR: u128 = 150_000_000_000_000_000; // 0.15 * SCALE (15%)
+
+/// The highest possible Quorum percentage (Ceiling), represented as a scaled value.
+pub const QUORUM_CEILING: u128 = 450_000_000_000_000_000; // 0.45 * SCALE (45%)
+
+/// The depth threshold (K) at which the quorum curve begins to flatten significantly.
+pub const DEPTH_THRESHOLD_K: u64 = 100_000_000; // $100 Million USD (Example threshold)
+
+// The difference between the ceiling and the floor
+const CEILING_MINUS_FLOOR: u128 = QUORUM_CEILING - QUORUM_FLOOR;
+
+/// Calculates the required Adaptive Quorum percentage based on the protocol's Depth Score.
+/// The logic implements a simplified decay curve to scale the quorum between 15% and 45%.
+///
+/// Every step rounds down (`Rounding::Floor`); the result is a value scaled by 10^18.
+pub fn calculate_adaptive_quorum(total_protocol_depth: u64) -> u128 {
+    // 1. Calculate the normalized Depth Score (x = Depth / K).
+    //    u64 inputs can never overflow the fixed-point range, so these conversions are total.
+    let depth = UFixed18::from_integer(total_protocol_depth as u128).unwrap_or(UFixed18::ZERO);
+    let threshold = UFixed18::from_integer(DEPTH_THRESHOLD_K as u128).unwrap_or(UFixed18::ONE);
+    let ratio_x = depth.checked_div(threshold, Rounding::Floor).unwrap_or(UFixed18::ZERO);
+
+    // A simplified, inverse power decay function is used to approximate the curve: f(x) = 1 / (1 + x)
+    let decay_term = UFixed18::ONE
+        .checked_add(ratio_x)
+        .and_then(|denominator| UFixed18::ONE.checked_div(denominator, Rounding::Floor))
+        .unwrap_or(UFixed18::ZERO);
+
+    // 2. Apply the decay term to the Quorum range:
+    let weighted_quorum = decay_term
+        .checked_mul(UFixed18::from_raw(CEILING_MINUS_FLOOR), Rounding::Floor)
+        .unwrap_or(UFixed18::ZERO);
+
+    // 3. Add the Floor to get the final Adaptive Quorum:
+    let final_quorum = QUORUM_FLOOR.saturating_add(weighted_quorum.raw());
+
+    final_quorum.min(QUORUM_CEILING)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixed_point::SCALE;
+
+    #[test]
+    // Test case: No depth at all. Quorum sits at the ceiling (45%).
+    fn test_zero_depth() {
+        assert_eq!(calculate_adaptive_quorum(0), QUORUM_CEILING);
+    }
+
+    #[test]
+    // Test case: Depth equals K. Quorum sits halfway between floor and ceiling (30%).
+    fn test_depth_at_threshold() {
+        assert_eq!(calculate_adaptive_quorum(DEPTH_THRESHOLD_K), 300_000_000_000_000_000);
+    }
+
+    #[test]
+    // Test case: Extreme depth. Quorum approaches (but never drops below) the floor.
+    fn test_max_depth() {
+        let quorum = calculate_adaptive_quorum(u64::MAX);
+        assert!(quorum >= QUORUM_FLOOR);
+        assert!(quorum < QUORUM_FLOOR + SCALE / 1_000_000);
+    }
+
+    #[test]
+    // Test case: The curve never increases as depth grows.
+    fn test_monotone_decreasing() {
+        let mut previous = calculate_adaptive_quorum(0);
+        for depth in [1, 1_000, 1_000_000, DEPTH_THRESHOLD_K, 10 * DEPTH_THRESHOLD_K, u64::MAX] {
+            let quorum = calculate_adaptive_quorum(depth);
+            assert!(quorum <= previous);
+            previous = quorum;
+        }
+    }
+}
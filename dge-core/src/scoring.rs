@@ -0,0 +1,147 @@
+// D-Metric Scoring
+// The core of the "verifiable conscious acts" philosophy: a purely deterministic,
+// auditable mapping from verifiable on-chain data to a 0-100 score.
+
+/// The default minimum D-Metric score a milestone must reach for its tranche to be disbursed.
+pub const D_METRIC_PASS_SCORE: u8 = 75;
+
+/// The highest possible D-Metric score.
+pub const MAX_D_METRIC_SCORE: u8 = 100;
+
+/// The pass/fail mapping from raw metric data to a D-Metric score.
+/// Deployments may tune these bands through governance; `MetricBands::DEFAULT` is the reference mapping.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MetricBands {
+    /// Metric value at or above which progress counts as excellent.
+    pub excellent_threshold: u64,
+    pub excellent_score: u8,
+    /// Metric value at or above which progress counts as good.
+    pub good_threshold: u64,
+    pub good_score: u8,
+    /// Score for anything below `good_threshold`.
+    pub failing_score: u8,
+}
+
+impl MetricBands {
+    pub const DEFAULT: Self = Self {
+        excellent_threshold: 1000,
+        excellent_score: 95, // Excellent verifiable progress
+        good_threshold: 500,
+        good_score: 80, // Good verifiable progress
+        failing_score: 60, // Failure to meet "Proof of Growth" threshold
+    };
+
+    /// Maps metric data to its score.
+    pub fn score(&self, metric_data: u64) -> u8 {
+        if metric_data >= self.excellent_threshold {
+            self.excellent_score
+        } else if metric_data >= self.good_threshold {
+            self.good_score
+        } else {
+            self.failing_score
+        }
+    }
+
+    /// Bands must be ordered (thresholds and scores non-decreasing) and scores within 0-100.
+    pub fn is_valid(&self) -> bool {
+        self.good_threshold <= self.excellent_threshold
+            && self.failing_score <= self.good_score
+            && self.good_score <= self.excellent_score
+            && self.excellent_score <= MAX_D_METRIC_SCORE
+    }
+}
+
+/// A monotone pass-score curve over a grant's milestones, so later tranches demand more
+/// traction: milestone `i` (1-based) must reach `base + step * (i - 1)`, capped at
+/// `MAX_D_METRIC_SCORE`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PassScoreCurve {
+    /// Pass score of the first milestone.
+    pub base: u8,
+    /// Increase per subsequent milestone. Zero gives a flat threshold.
+    pub step: u8,
+}
+
+impl PassScoreCurve {
+    /// The flat default: every milestone at `D_METRIC_PASS_SCORE`.
+    pub const FLAT: Self = Self { base: D_METRIC_PASS_SCORE, step: 0 };
+
+    /// The pass score for the 1-based `milestone_index`.
+    pub fn pass_score(&self, milestone_index: u8) -> u8 {
+        let steps = milestone_index.saturating_sub(1) as u32;
+        let raised = self.base as u32 + self.step as u32 * steps;
+        raised.min(MAX_D_METRIC_SCORE as u32) as u8
+    }
+
+    /// The curve must start at an attainable score.
+    pub fn is_valid(&self) -> bool {
+        self.base <= MAX_D_METRIC_SCORE
+    }
+}
+
+/// Calculates the deterministic D-Metric score based on verifiable on-chain data,
+/// using the reference `MetricBands::DEFAULT` mapping.
+pub fn calculate_d_metric(metric_data: u64) -> u8 {
+    // Placeholder implementation:
+    // This is where we would check things like:
+    // 1. Has the associated BPF program been deployed?
+    // 2. Has the project received X unique transactions?
+    // 3. Has the team updated a verifiable on-chain registry?
+    MetricBands::DEFAULT.score(metric_data)
+}
+
+/// Returns true when a score meets the default pass threshold.
+pub fn is_passing_score(score: u8) -> bool {
+    meets_pass_score(score, D_METRIC_PASS_SCORE)
+}
+
+/// Returns true when a score meets a configured pass threshold.
+pub fn meets_pass_score(score: u8, pass_score: u8) -> bool {
+    score >= pass_score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Test case: Each metric band maps to its score and pass/fail outcome.
+    fn test_metric_bands() {
+        assert_eq!(calculate_d_metric(0), 60);
+        assert_eq!(calculate_d_metric(499), 60);
+        assert_eq!(calculate_d_metric(500), 80);
+        assert_eq!(calculate_d_metric(1000), 95);
+        assert!(!is_passing_score(calculate_d_metric(499)));
+        assert!(is_passing_score(calculate_d_metric(500)));
+    }
+
+    #[test]
+    // Test case: Custom bands and pass scores are honoured; unordered bands are rejected.
+    fn test_custom_bands() {
+        let bands = MetricBands { excellent_threshold: 50, excellent_score: 90, good_threshold: 10, good_score: 70, failing_score: 0 };
+        assert!(bands.is_valid());
+        assert_eq!(bands.score(10), 70);
+        assert!(meets_pass_score(bands.score(10), 70));
+        assert!(!meets_pass_score(bands.score(9), 70));
+
+        let unordered = MetricBands { good_threshold: 100, ..bands };
+        assert!(!unordered.is_valid());
+        assert!(MetricBands::DEFAULT.is_valid());
+    }
+
+    #[test]
+    // Test case: The curve rises by `step` per milestone, never decreases, and caps at 100.
+    fn test_pass_score_curve() {
+        let curve = PassScoreCurve { base: 70, step: 5 };
+        assert_eq!(curve.pass_score(1), 70);
+        assert_eq!(curve.pass_score(2), 75);
+        assert_eq!(curve.pass_score(7), 100);
+        assert_eq!(curve.pass_score(u8::MAX), 100);
+        for milestone_index in 1..u8::MAX {
+            assert!(curve.pass_score(milestone_index) <= curve.pass_score(milestone_index + 1));
+        }
+
+        assert_eq!(PassScoreCurve::FLAT.pass_score(16), D_METRIC_PASS_SCORE);
+        assert!(!PassScoreCurve { base: 101, step: 0 }.is_valid());
+    }
+}
@@ -0,0 +1,30 @@
+// Test Support
+// A stand-in signature scheme shared by the attestation and certificate tests. dge-core carries
+// no cryptography, so tests sign with a scheme where a valid "signature" is the signer's key
+// followed by a byte-wise fold of the whole message: any changed byte breaks it.
+
+use crate::certificate::SignatureVerifier;
+
+pub struct MockVerifier;
+
+impl SignatureVerifier for MockVerifier {
+    fn verify(&self, message: &[u8], public_key: &[u8; 32], signature: &[u8; 64]) -> bool {
+        signature[..32] == public_key[..] && signature[32..] == digest(message)
+    }
+}
+
+/// Signs `message` as `key` under the mock scheme.
+pub fn sign(message: &[u8], key: &[u8; 32]) -> [u8; 64] {
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(key);
+    signature[32..].copy_from_slice(&digest(message));
+    signature
+}
+
+fn digest(message: &[u8]) -> [u8; 32] {
+    let mut digest = [0u8; 32];
+    for (i, byte) in message.iter().enumerate() {
+        digest[i % 32] = digest[i % 32].wrapping_mul(31).wrapping_add(*byte);
+    }
+    digest
+}
@@ -0,0 +1,68 @@
+// JavaScript Bindings (`wasm` feature)
+// Exposes the quorum and bond calculators to web frontends through wasm-bindgen, so the UI
+// shows exactly the numbers the chain enforces. Build for the browser with:
+//
+//   cargo build --release --target wasm32-unknown-unknown --features wasm
+//   wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/dge_core.wasm
+//
+// Values scaled by 10^18 can exceed 2^64, so they cross the boundary as decimal strings.
+// Token amounts are u64 and arrive in JavaScript as BigInt.
+
+use wasm_bindgen::prelude::*;
+
+use crate::bond::{
+    self, apply_collateral_haircut, apply_repeat_offender_escalation, usd_to_token_base_units, BondError, BOND_ROUNDING,
+};
+use crate::error::DgeErrorCode;
+use crate::quorum;
+
+/// The Adaptive Quorum for a protocol Depth Score, as a decimal string scaled by 10^18
+/// (e.g. "450000000000000000" for 45%).
+#[wasm_bindgen(js_name = calculateAdaptiveQuorum)]
+pub fn calculate_adaptive_quorum(total_protocol_depth: u64) -> String {
+    quorum::calculate_adaptive_quorum(total_protocol_depth).to_string()
+}
+
+/// The Tier 1 Builder Bond in whole FST at a price given as a decimal string scaled by 10^18.
+#[wasm_bindgen(js_name = getBuilderBondAmount)]
+pub fn get_builder_bond_amount(fst_price_in_usd_scaled: &str) -> Result<u64, JsError> {
+    let price = parse_scaled(fst_price_in_usd_scaled)?;
+    bond::get_builder_bond_amount(price).map_err(bond_error)
+}
+
+/// The bond, in collateral base units, that `initialize_grant` requires: `bond_usd` (the tier's
+/// governance-set requirement) at the given price, rounded up, then grossed up by the haircut.
+#[wasm_bindgen(js_name = getCollateralBondAmount)]
+pub fn get_collateral_bond_amount(
+    bond_usd: u64,
+    collateral_price_in_usd_scaled: &str,
+    token_decimals: u8,
+    haircut_bps: u16,
+) -> Result<u64, JsError> {
+    let price = parse_scaled(collateral_price_in_usd_scaled)?;
+    usd_to_token_base_units(bond_usd, price, token_decimals, BOND_ROUNDING)
+        .and_then(|face_value| apply_collateral_haircut(face_value, haircut_bps))
+        .map_err(bond_error)
+}
+
+/// The effective bond for a builder with `prior_liquidations` liquidations on record: the
+/// result of `getCollateralBondAmount`, escalated by the governance-set repeat-offender factor.
+#[wasm_bindgen(js_name = getEscalatedBondAmount)]
+pub fn get_escalated_bond_amount(
+    bond_amount: u64,
+    prior_liquidations: u32,
+    escalation_bps: u16,
+    max_multiplier_bps: u16,
+) -> Result<u64, JsError> {
+    apply_repeat_offender_escalation(bond_amount, prior_liquidations, escalation_bps, max_multiplier_bps).map_err(bond_error)
+}
+
+fn parse_scaled(value: &str) -> Result<u128, JsError> {
+    value.parse().map_err(|_| JsError::new("expected a non-negative integer scaled by 10^18"))
+}
+
+/// Reports a bond failure with its stable cross-chain code, e.g. "DGE-100: ...".
+fn bond_error(error: BondError) -> JsError {
+    let code = DgeErrorCode::from(error);
+    JsError::new(&format!("DGE-{}: {}", code.code(), code.description()))
+}
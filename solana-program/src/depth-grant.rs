@@ -3,9 +3,21 @@
 //! on Solana using the Builder Bond (BB) and the D-Metric for conditional payouts.
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
+use dge_core::bond::{
+    apply_collateral_haircut, apply_repeat_offender_escalation, usd_to_token_base_units, GrantTier, BOND_ROUNDING, HAIRCUT_BPS_DENOMINATOR,
+    TIER_1_BUILDER_BOND_USD, TIER_2_BUILDER_BOND_USD, TIER_2_MIN_GRANT_TOKENS, TIER_3_BUILDER_BOND_USD, TIER_3_MIN_GRANT_TOKENS,
+};
+use dge_core::error::DgeErrorCode;
+use dge_core::fixed_point::{mul_div, Rounding};
+use dge_core::payout::{proportional_tranche_amount, tranche_amount};
+use dge_core::scoring::{meets_pass_score, MetricBands, PassScoreCurve, D_METRIC_PASS_SCORE, MAX_D_METRIC_SCORE};
 
 // This is the program ID for the DGE.
-declare_id!("DGE1111111111111111111111111111111111111111111111111111111111");
+declare_id!("DGEGrantEngine11111111111111111111111111111");
 
 /// The primary DGE program module.
 #[program]
@@ -16,12 +28,65 @@ pub mod depth_protocol_grant_engine {
     /// The D-Metric philosophy ("Proof of Growth" or "verifiable conscious acts")
     /// is encoded here by defining the required initial criteria (e.g., code commits,
     /// governance vote participation, initial deposit).
+    ///
+    /// The bond is posted in any whitelisted collateral token (see `register_collateral`) and must
+    /// cover the USD requirement of the grant's tier (set in `ProgramConfig`), scaled by
+    /// that token's haircut, at the price last posted by the token's oracle. Prices older than
+    /// `config.max_price_age_seconds` are rejected.
+    ///
+    /// Milestones cannot be claimed before `start_ts`, which must lie between now and
+    /// `MAX_GRANT_START_DELAY_SECONDS` ahead. If the DAO has not funded the grant by
+    /// `funding_deadline_ts`, the builder may reclaim the bond with `reclaim_unfunded_grant`.
     pub fn initialize_grant(
         ctx: Context<InitializeGrant>,
         total_grant_amount: u64,
         milestone_tranches: u8,
         builder_bond_amount: u64,
+        start_ts: i64,
+        funding_deadline_ts: i64,
     ) -> Result<()> {
+        if milestone_tranches == 0 || milestone_tranches as usize > MAX_MILESTONES {
+            return err!(DGEError::InvalidMilestoneCount);
+        }
+        let now = Clock::get()?.unix_timestamp;
+        let latest_start = now.checked_add(MAX_GRANT_START_DELAY_SECONDS).ok_or(DGEError::ArithmeticOverflow)?;
+        if funding_deadline_ts < now || start_ts < now || start_ts > latest_start {
+            return err!(DGEError::InvalidGrantTimeline);
+        }
+
+        let collateral = &ctx.accounts.collateral;
+        if !collateral.is_enabled {
+            return err!(DGEError::CollateralDisabled);
+        }
+        let collateral_price_in_usd_scaled = collateral.fresh_price(now, ctx.accounts.config.max_price_age_seconds)?;
+
+        // --- TIER CHECK ---
+        // The tier follows from the grant amount. The posted bond must match that tier's bond in
+        // collateral base units (rounded up), grossed up by the collateral haircut, then escalated
+        // for the builder's prior liquidations.
+        let config = &ctx.accounts.config;
+        let grant_tier = config.tier_for_grant_amount(total_grant_amount);
+        let bond_usd = config.bond_usd_for_tier(grant_tier);
+        let required_bond = usd_to_token_base_units(
+            bond_usd,
+            collateral_price_in_usd_scaled,
+            ctx.accounts.bond_mint.decimals,
+            BOND_ROUNDING,
+        )
+        .and_then(|face_value| apply_collateral_haircut(face_value, collateral.haircut_bps))
+        .and_then(|bond| {
+            apply_repeat_offender_escalation(
+                bond,
+                ctx.accounts.builder_profile.liquidations,
+                config.bond_escalation_bps,
+                config.max_bond_multiplier_bps,
+            )
+        })
+        .map_err(|_| DGEError::BondCalculationFailed)?;
+        if builder_bond_amount < required_bond {
+            return err!(DGEError::InsufficientBuilderBond);
+        }
+
         let grant = &mut ctx.accounts.grant;
 
         // --- THE BUILDER BOND (BB) MECHANISM ---
@@ -29,24 +94,97 @@ pub mod depth_protocol_grant_engine {
         grant.builder = ctx.accounts.builder.key();
         grant.total_grant_amount = total_grant_amount;
         grant.builder_bond_amount = builder_bond_amount; // The amount staked for the BB
+        grant.bond_mint = ctx.accounts.bond_mint.key();
+        grant.tier = grant_tier as u8;
         grant.tranches_completed = 0;
         grant.total_tranches = milestone_tranches;
+        grant.amount_disbursed = 0;
+        grant.start_ts = start_ts;
+        grant.funding_deadline_ts = funding_deadline_ts;
+        grant.parent_grant = Pubkey::default();
+        grant.sub_grant_allocated = 0;
+        grant.is_funded = false;
+        grant.total_covered = 0;
+        grant.missed_pass_score = false;
         grant.is_liquidated = false;
+        grant.builder_profile = ctx.accounts.builder_profile.key();
+        grant.origin_builder = grant.builder;
+        grant.bump = ctx.bumps.grant;
+
+        // --- BUILDER PROFILE ---
+        let builder_profile = &mut ctx.accounts.builder_profile;
+        grant.grant_nonce = builder_profile.grant_nonce;
+        builder_profile.grant_nonce = builder_profile
+            .grant_nonce
+            .checked_add(1)
+            .ok_or(DGEError::ArithmeticOverflow)?;
+        builder_profile.grants_started = builder_profile
+            .grants_started
+            .checked_add(1)
+            .ok_or(DGEError::ArithmeticOverflow)?;
 
-        // In a real-world scenario, a transfer instruction would move the builder_bond_amount
-        // from the builder's account to the program's vault or the Grant account.
-        msg!("Grant initialized. Builder Bond of {} staked successfully.", builder_bond_amount);
+        // --- MILESTONE SCHEDULE ---
+        // Pass scores follow the configured curve (rising per milestone if `pass_score_step` is set);
+        // governance may still tune them per grant with `set_milestone_schedule`.
+        let curve = ctx.accounts.config.pass_score_curve();
+        let milestone_schedule = &mut ctx.accounts.milestone_schedule;
+        milestone_schedule.grant = grant.key();
+        milestone_schedule.required_scores = (1..=milestone_tranches).map(|index| curve.pass_score(index)).collect();
+        milestone_schedule.bump = ctx.bumps.milestone_schedule;
+
+        // --- REGISTRY APPEND ---
+        // Index the grant so clients can enumerate grants without getProgramAccounts scans.
+        let registry = &mut ctx.accounts.registry;
+        let registry_bucket = &mut ctx.accounts.registry_bucket;
+        if registry_bucket.grants.len() >= GRANTS_PER_REGISTRY_BUCKET {
+            return err!(DGEError::RegistryBucketFull);
+        }
+        grant.registry_index = registry.grant_count;
+        registry_bucket.grants.push(grant.key());
+        registry.grant_count = registry.grant_count.checked_add(1).ok_or(DGEError::ArithmeticOverflow)?;
+
+        // --- BOND CUSTODY ---
+        // Move the bond from the builder into the program-controlled vault for this collateral.
+        // All grant state is written first, so nothing depends on state after the token CPI.
+        token::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.builder_token_account.to_account_info(),
+                    mint: ctx.accounts.bond_mint.to_account_info(),
+                    to: ctx.accounts.bond_vault.to_account_info(),
+                    authority: ctx.accounts.builder.to_account_info(),
+                },
+            ),
+            builder_bond_amount,
+            ctx.accounts.bond_mint.decimals,
+        )?;
+
+        msg!("Grant initialized. Builder Bond of {} ({}) staked successfully.", builder_bond_amount, grant.bond_mint);
         Ok(())
     }
 
     /// Attempts to disburse the next grant tranche upon milestone completion.
     /// This function performs the core Cypherpunk accountability check via the D-Metric.
     /// Disbursal is **conditional** and **automated**.
+    ///
+    /// `on_chain_metric_data` is only trusted when at least `verifier_set.threshold` distinct
+    /// registered verifiers co-sign the transaction; pass them as signer `remaining_accounts`.
+    ///
+    /// Tranches are paid out of the grant's escrow vault, so the grant must be funded first
+    /// (see `fund_grant`). Each paid tranche leaves a `TrancheReceipt` PDA, rent paid by the builder.
+    ///
+    /// If governance sets a `partial_payout_floor`, a score between the floor and the milestone's
+    /// pass score releases a proportional share of the tranche; the shortfall stays in escrow
+    /// and returns to the treasury through `refund_grant_vault`.
+    ///
+    /// Must be the top-level instruction: calls arriving through another program are rejected.
     pub fn complete_milestone_and_payout(
         ctx: Context<MilestonePayout>,
         milestone_index: u8,
         on_chain_metric_data: u64, // Placeholder for verifiable data (e.g., unique user count)
     ) -> Result<()> {
+        require_top_level_call(&ctx.accounts.instructions_sysvar)?;
         let grant = &mut ctx.accounts.grant;
 
         // Check if the grant is already marked for liquidation.
@@ -54,34 +192,144 @@ pub mod depth_protocol_grant_engine {
             return err!(DGEError::GrantLiquidated);
         }
 
-        // Ensure the correct sequence of milestones.
-        if milestone_index != grant.tranches_completed + 1 {
+        // Payouts stay frozen while a milestone challenge is open.
+        if grant.is_frozen {
+            return err!(DGEError::GrantFrozen);
+        }
+
+        // Ensure the correct sequence of milestones, and that a tranche is still owed.
+        if grant.tranches_completed >= grant.total_tranches {
+            return err!(DGEError::TrancheOverflow);
+        }
+        let next_milestone = grant.tranches_completed.checked_add(1).ok_or(DGEError::TrancheOverflow)?;
+        if milestone_index != next_milestone {
             return err!(DGEError::MilestoneOutOfOrder);
         }
+        let now = Clock::get()?.unix_timestamp;
+        if now < grant.start_ts {
+            return err!(DGEError::GrantNotStarted);
+        }
+
+        // --- METRIC ATTESTATION (M-of-N verifiers) ---
+        let attestations = ctx.accounts.verifier_set.count_attestations(ctx.remaining_accounts);
+        if attestations < ctx.accounts.verifier_set.threshold as usize {
+            return err!(DGEError::InsufficientVerifierSignatures);
+        }
+        if grant.parent_grant != Pubkey::default() {
+            require_umbrella_approval(&grant.parent_grant, ctx.remaining_accounts)?;
+        }
 
         // --- THE D-METRIC CHECK (Proof of Growth / Verifiable Conscious Acts) ---
         // This logic replaces subjective human review with a deterministic, auditable check.
-        // The D-Metric minimum threshold is set here (e.g., a score of 75/100 or a specific metric value).
-        let d_metric_score = calculate_d_metric(on_chain_metric_data);
+        // The metric-to-score bands are governance-set in `ProgramConfig`, the pass score for
+        // this milestone in the grant's `MilestoneSchedule`; both are evaluated by `dge_core::scoring`.
+        let config = &ctx.accounts.config;
+        let d_metric_score = config.metric_bands().score(on_chain_metric_data);
+        let required_score = ctx.accounts.milestone_schedule.required_score(milestone_index)?;
+        let floor_score = config.partial_payout_floor_for(required_score);
+        let builder_profile = &mut ctx.accounts.builder_profile;
+        builder_profile.record_score(d_metric_score)?;
 
-        if d_metric_score < 75 {
+        // Without a configured floor, `floor_score` is the pass score and this is plain pass/fail.
+        if !meets_pass_score(d_metric_score, required_score) && d_metric_score <= floor_score {
             // D-Metric failed: Trigger Builder Bond liquidation and pause all future payouts.
             grant.is_liquidated = true;
-            msg!("D-Metric failure (Score: {}). Builder Bond Liquidation Triggered. Grant Paused.", d_metric_score);
+            builder_profile.record_liquidation()?;
+            emit!(DMetricLiquidation {
+                grant: grant.key(),
+                milestone_index,
+                d_metric_score,
+                required_score,
+            });
+            msg!(
+                "D-Metric failure (Score: {}, required: {}). Builder Bond Liquidation Triggered. Grant Paused.",
+                d_metric_score, required_score
+            );
 
-            // In a real program, an instruction would handle the liquidation of the bond,
-            // returning it to the DAO treasury.
-            return err!(DGEError::DMetricFailed);
+            // The bond stays in its vault until governance sweeps it with `sweep_liquidated_bond`.
+            // The instruction succeeds so the liquidation (and the profile update) is persisted;
+            // returning an error here would roll both back. Nothing was delivered, so the
+            // receipt created for this milestone is closed again and its rent refunded.
+            ctx.accounts.receipt.close(ctx.accounts.builder.to_account_info())?;
+            return Ok(());
         }
 
         // D-Metric passed: Proceed with tranche disbursement.
+        // The challenge window for this milestone opens now.
+        // Every tranche but the last pays the nominal share; the final one pays exactly what is
+        // left of the grant, so the integer-division remainder is never stranded in escrow.
+        let full_tranche = tranche_amount(
+            grant.total_grant_amount,
+            grant.total_tranches,
+            milestone_index,
+            grant.amount_disbursed,
+        )
+        .ok_or(DGEError::TrancheOverflow)?;
+        // A score short of the pass score releases only its proportional share. The whole
+        // tranche is settled either way; the shortfall is forfeited to the treasury.
+        let tranche_amount = proportional_tranche_amount(full_tranche, d_metric_score, floor_score, required_score)
+            .ok_or(DGEError::ArithmeticOverflow)?;
+        let shortfall = full_tranche - tranche_amount;
+        if shortfall > 0 {
+            // A failed score check closes the grant to new cover (see `purchase_coverage`).
+            grant.missed_pass_score = true;
+        }
         grant.tranches_completed = milestone_index;
-        let tranche_amount = grant.total_grant_amount / grant.total_tranches as u64;
+        grant.last_payout_ts = now;
+        grant.amount_disbursed = grant
+            .amount_disbursed
+            .checked_add(full_tranche)
+            .ok_or(DGEError::ArithmeticOverflow)?;
+        let grant_vault = &mut ctx.accounts.grant_vault;
+
+        // --- INSURANCE FUND FEE ---
+        // A basis-point cut of every tranche is routed to the InsuranceFund PDA,
+        // building a pool governance can use to compensate victims of failed grants.
+        let fee_amount = config.insurance_fee_for(tranche_amount)?;
+        let insurance_fund = &mut ctx.accounts.insurance_fund;
+        let builder_amount = tranche_amount.checked_sub(fee_amount).ok_or(DGEError::ArithmeticOverflow)?;
+        insurance_fund.total_collected = insurance_fund
+            .total_collected
+            .checked_add(fee_amount)
+            .ok_or(DGEError::ArithmeticOverflow)?;
+
+        builder_profile.total_disbursed = builder_profile
+            .total_disbursed
+            .checked_add(builder_amount)
+            .ok_or(DGEError::ArithmeticOverflow)?;
+        if grant.tranches_completed == grant.total_tranches {
+            builder_profile.grants_completed = builder_profile
+                .grants_completed
+                .checked_add(1)
+                .ok_or(DGEError::ArithmeticOverflow)?;
+        }
 
-        // In a real program, a CPI would transfer the tranche_amount from the
-        // DAO's vault to the builder's account.
-        msg!("D-Metric passed (Score: {}). Tranche {} of {} disbursed: {} SOL.",
-            d_metric_score, grant.tranches_completed, grant.total_tranches, tranche_amount
+        grant_vault.disbursed_amount = grant_vault
+            .disbursed_amount
+            .checked_add(tranche_amount)
+            .ok_or(DGEError::ArithmeticOverflow)?;
+
+        // --- PROOF OF DELIVERY ---
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.grant = grant.key();
+        receipt.builder = grant.builder;
+        receipt.milestone_index = milestone_index;
+        receipt.tranche_amount = tranche_amount;
+        receipt.builder_amount = builder_amount;
+        receipt.d_metric_score = d_metric_score;
+        receipt.paid_at = now;
+        receipt.is_revoked = false;
+        receipt.bump = ctx.bumps.receipt;
+
+        // Every state change above is committed before any lamports move. The vault, builder, and
+        // fund balances are all debited or credited directly: the vault is owned by this program
+        // and credits need no ownership.
+        let vault_info = grant_vault.to_account_info();
+        move_lamports(&vault_info, &ctx.accounts.builder.to_account_info(), builder_amount)?;
+        move_lamports(&vault_info, &insurance_fund.to_account_info(), fee_amount)?;
+
+        msg!("D-Metric passed (Score: {}). Tranche {} of {} disbursed: {} SOL (insurance fee: {} SOL, forfeited: {} SOL).",
+            d_metric_score, grant.tranches_completed, grant.total_tranches, builder_amount, fee_amount, shortfall
         );
 
         Ok(())
@@ -89,7 +337,9 @@ pub mod depth_protocol_grant_engine {
 
     /// Function to explicitly liquidate the Builder Bond if the D-Metric fails an off-chain audit (rare fallback).
     /// This is an emergency function and should be guarded by a secure DAO multisig.
+    /// Must be the top-level instruction: calls arriving through another program are rejected.
     pub fn liquidate_bond(ctx: Context<LiquidateBond>) -> Result<()> {
+        require_top_level_call(&ctx.accounts.instructions_sysvar)?;
         let grant = &mut ctx.accounts.grant;
         if grant.is_liquidated {
             msg!("Grant is already liquidated.");
@@ -98,95 +348,2519 @@ pub mod depth_protocol_grant_engine {
 
         // Mark the grant for liquidation and pause payouts.
         grant.is_liquidated = true;
+        ctx.accounts.builder_profile.record_liquidation()?;
 
         // Log the event for maximum transparency and auditability.
         msg!("Builder Bond Liquidation initiated by DAO Quorum.");
 
-        // Real-world: Transfer the grant funds back to the DAO treasury.
+        // Undisbursed escrow is returned to the treasury with `refund_grant_vault`.
 
         Ok(())
     }
-}
 
-// --- D-METRIC LOGIC IMPLEMENTATION ---
+    /// Challenges the most recently paid milestone, locking `stake` lamports in a Challenge PDA.
+    /// Open to anyone within `config.challenge_window_seconds` of the payout. All further payouts
+    /// are frozen until the DAO resolves the dispute with `resolve_challenge`.
+    pub fn challenge_milestone(ctx: Context<ChallengeMilestone>, milestone_index: u8, stake: u64) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let grant = &mut ctx.accounts.grant;
+
+        if grant.is_liquidated {
+            return err!(DGEError::GrantLiquidated);
+        }
+        if grant.is_frozen {
+            return err!(DGEError::GrantFrozen);
+        }
+        if milestone_index == 0 || milestone_index != grant.tranches_completed {
+            return err!(DGEError::MilestoneNotChallengeable);
+        }
+        let now = Clock::get()?.unix_timestamp;
+        let window_end = grant
+            .last_payout_ts
+            .checked_add(config.challenge_window_seconds)
+            .ok_or(DGEError::ArithmeticOverflow)?;
+        if now > window_end {
+            return err!(DGEError::ChallengeWindowClosed);
+        }
+        if stake < config.min_challenge_stake {
+            return err!(DGEError::ChallengeStakeTooLow);
+        }
+
+        grant.is_frozen = true;
 
-/// Calculates the deterministic D-Metric score based on verifiable on-chain data.
-/// The core of the "verifiable conscious acts" philosophy is contained here.
-/// This function must be purely deterministic and auditable.
-fn calculate_d_metric(metric_data: u64) -> u8 {
-    // Placeholder implementation:
-    // This is where we would check things like:
-    // 1. Has the associated BPF program been deployed?
-    // 2. Has the project received X unique transactions?
-    // 3. Has the team updated a verifiable on-chain registry?
+        let challenge = &mut ctx.accounts.challenge;
+        challenge.grant = grant.key();
+        challenge.challenger = ctx.accounts.challenger.key();
+        challenge.milestone_index = milestone_index;
+        challenge.stake = stake;
+        challenge.bump = ctx.bumps.challenge;
 
-    if metric_data >= 1000 {
-        return 95; // Excellent verifiable progress
-    } else if metric_data >= 500 {
-        return 80; // Good verifiable progress
-    } else {
-        return 60; // Failure to meet "Proof of Growth" threshold
+        // Lock the challenger's stake inside the Challenge PDA (on top of its rent).
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.challenger.to_account_info(),
+                    to: ctx.accounts.challenge.to_account_info(),
+                },
+            ),
+            stake,
+        )?;
+
+        emit!(MilestoneChallenged {
+            grant: grant.key(),
+            challenger: ctx.accounts.challenger.key(),
+            milestone_index,
+            stake,
+        });
+        msg!("Milestone {} challenged with a stake of {} lamports. Payouts frozen.", milestone_index, stake);
+        Ok(())
     }
-}
 
+    /// Resolves an open milestone challenge. Governance only.
+    /// * Upheld: the grant is liquidated, the challenger recovers their stake and is paid
+    ///   `config.challenger_bond_share_bps` of the Builder Bond; the rest of the bond stays in
+    ///   the collateral vault.
+    /// * Rejected: the challenger's stake compensates the builder and payouts resume.
+    /// The Challenge PDA is closed to the challenger either way.
+    pub fn resolve_challenge(ctx: Context<ResolveChallenge>, upheld: bool) -> Result<()> {
+        let grant = &mut ctx.accounts.grant;
+        let challenge = &ctx.accounts.challenge;
+        grant.is_frozen = false;
 
-// --- ACCOUNTS & DATA STRUCTURES ---
+        if upheld {
+            // `liquidate_bond` may have liquidated the grant while the challenge was open.
+            if !grant.is_liquidated {
+                grant.is_liquidated = true;
+                ctx.accounts.builder_profile.record_liquidation()?;
+            }
+            ctx.accounts.receipt.is_revoked = true;
 
-/// Context for initializing a new grant.
-#[derive(Accounts)]
-pub struct InitializeGrant<'info> {
-    #[account(init, payer = builder, space = 8 + Grant::LEN)]
-    pub grant: Account<'info, Grant>,
-    /// CHECK: The builder is the one who pays for the account creation and stakes the bond.
-    #[account(mut)]
-    pub builder: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
+            // The challenger's reward comes out of the bond, which is written down before the CPI.
+            let reward = ctx.accounts.config.challenger_reward_for(grant.builder_bond_amount)?;
+            grant.builder_bond_amount = grant.builder_bond_amount.checked_sub(reward).ok_or(DGEError::ArithmeticOverflow)?;
+            let vault_authority_seeds: &[&[u8]] = &[BOND_VAULT_AUTHORITY_SEED, &[ctx.bumps.vault_authority]];
+            token::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.bond_vault.to_account_info(),
+                        mint: ctx.accounts.bond_mint.to_account_info(),
+                        to: ctx.accounts.challenger_token_account.to_account_info(),
+                        authority: ctx.accounts.vault_authority.to_account_info(),
+                    },
+                    &[vault_authority_seeds],
+                ),
+                reward,
+                ctx.accounts.bond_mint.decimals,
+            )?;
+            msg!("Challenge upheld. Builder Bond Liquidation Triggered. Grant Paused. Challenger paid {} of the bond.", reward);
+        } else {
+            // The Challenge PDA is owned by this program, so the stake can be moved directly.
+            move_lamports(
+                &ctx.accounts.challenge.to_account_info(),
+                &ctx.accounts.builder.to_account_info(),
+                challenge.stake,
+            )?;
+            msg!("Challenge rejected. {} lamports paid to the builder. Payouts resumed.", challenge.stake);
+        }
 
-/// Context for milestone completion and payout.
-#[derive(Accounts)]
-pub struct MilestonePayout<'info> {
-    #[account(mut, has_one = builder)]
-    pub grant: Account<'info, Grant>,
-    pub builder: Signer<'info>,
-    /// CHECK: The DAO authority for treasury disbursement (used in a real CPI).
-    #[account(mut)]
-    pub dao_treasury: UncheckedAccount<'info>,
-}
+        emit!(ChallengeResolved {
+            grant: grant.key(),
+            challenger: challenge.challenger,
+            milestone_index: challenge.milestone_index,
+            upheld,
+            stake: challenge.stake,
+        });
+        Ok(())
+    }
 
-/// Context for bond liquidation (emergency fallback).
-#[derive(Accounts)]
-pub struct LiquidateBond<'info> {
-    #[account(mut)]
-    pub grant: Account<'info, Grant>,
-    /// CHECK: Must be a signer from the DAO's multisig/governance program.
-    pub dao_authority: Signer<'info>,
-}
+    /// Rotates the builder authority of a grant to a new key (e.g., after key loss or a team change).
+    /// Requires both the current builder and the DAO authority to sign. Every `has_one = builder`
+    /// constraint resolves against `grant.builder`, so updating that single field moves all
+    /// builder-gated instructions over to the new key.
+    pub fn rotate_builder_authority(ctx: Context<RotateBuilderAuthority>, new_builder: Pubkey) -> Result<()> {
+        let grant = &mut ctx.accounts.grant;
 
-/// The main Grant Account data structure.
-#[account]
-pub struct Grant {
-    pub builder: Pubkey,            // 32
-    pub total_grant_amount: u64,    // 8
-    pub builder_bond_amount: u64,   // 8
-    pub tranches_completed: u8,     // 1
-    pub total_tranches: u8,         // 1
-    pub is_liquidated: bool,        // 1
-    // Padding to ensure future expansion: ~40 bytes
-}
+        if new_builder == grant.builder || new_builder == Pubkey::default() {
+            return err!(DGEError::InvalidBuilderAuthority);
+        }
 
-impl Grant {
-    pub const LEN: usize = 32 + 8 + 8 + 1 + 1 + 1 + 40;
-}
+        let old_builder = grant.builder;
+        grant.builder = new_builder;
 
-// --- ERROR HANDLING ---
+        emit!(BuilderAuthorityRotated {
+            grant: grant.key(),
+            old_builder,
+            new_builder,
+            dao_authority: ctx.accounts.dao_authority.key(),
+        });
+        msg!("Builder authority rotated from {} to {}.", old_builder, new_builder);
+        Ok(())
+    }
 
-#[error_code]
-pub enum DGEError {
-    #[msg("The calculated D-Metric score is below the required threshold, triggering bond liquidation.")]
-    DMetricFailed,
-    #[msg("Cannot disburse a tranche; the grant has been liquidated and paused.")]
-    GrantLiquidated,
-    #[msg("Milestone submission is out of the required sequential order.")]
+    /// Read-only standing check intended to be called via CPI by downstream programs
+    /// (e.g., a launchpad gating features on an active DGE grant).
+    /// Fails if the grant has been liquidated; otherwise returns a `GrantStatus`
+    /// snapshot through the transaction return data.
+    /// An unfunded grant is still in good standing (its bond is posted and it can yet be
+    /// funded), so it does not fail here; callers gating on escrowed funds check `is_funded`.
+    pub fn verify_grant_standing(ctx: Context<VerifyGrantStanding>) -> Result<GrantStatus> {
+        let grant = &ctx.accounts.grant;
+
+        if grant.is_liquidated {
+            return err!(DGEError::GrantLiquidated);
+        }
+
+        Ok(grant.status())
+    }
+
+    /// Creates the singleton ProgramConfig PDA holding every governance tunable.
+    /// Only the program's upgrade authority may call this, which prevents anyone from
+    /// front-running deployment and installing their own governance authority.
+    pub fn initialize_config(ctx: Context<InitializeConfig>, governance_authority: Pubkey, params: ConfigParams) -> Result<()> {
+        params.validate()?;
+
+        let config = &mut ctx.accounts.config;
+        config.governance_authority = governance_authority;
+        config.apply(&params);
+        config.bump = ctx.bumps.config;
+
+        msg!("Program config initialized. Governance authority: {}.", governance_authority);
+        Ok(())
+    }
+
+    /// Replaces every tunable in the ProgramConfig. Governance only.
+    pub fn update_config(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+        params.validate()?;
+        ctx.accounts.config.apply(&params);
+
+        msg!("Program config updated.");
+        Ok(())
+    }
+
+    /// Creates the builder's reputation record. Must exist before the builder's first grant.
+    pub fn initialize_builder_profile(ctx: Context<InitializeBuilderProfile>) -> Result<()> {
+        let builder_profile = &mut ctx.accounts.builder_profile;
+        builder_profile.builder = ctx.accounts.builder.key();
+        builder_profile.grant_nonce = 0;
+        builder_profile.grants_started = 0;
+        builder_profile.grants_completed = 0;
+        builder_profile.liquidations = 0;
+        builder_profile.total_disbursed = 0;
+        builder_profile.score_sum = 0;
+        builder_profile.scores_recorded = 0;
+        builder_profile.d_metric = 0;
+        builder_profile.bump = ctx.bumps.builder_profile;
+
+        msg!("Builder Profile created for {}.", builder_profile.builder);
+        Ok(())
+    }
+
+    /// Creates the singleton grant Registry PDA together with its first index bucket.
+    pub fn initialize_registry(ctx: Context<InitializeRegistry>) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        registry.grant_count = 0;
+        registry.bucket_count = 1;
+        registry.bump = ctx.bumps.registry;
+
+        let registry_bucket = &mut ctx.accounts.registry_bucket;
+        registry_bucket.bucket_index = 0;
+        registry_bucket.grants = Vec::new();
+        registry_bucket.bump = ctx.bumps.registry_bucket;
+
+        msg!("Grant Registry initialized.");
+        Ok(())
+    }
+
+    /// Opens the next registry bucket once the current one is full. Permissionless: the caller
+    /// only pays rent, and clients typically prepend this to the `initialize_grant` transaction
+    /// whenever `registry.grant_count` is a multiple of `GRANTS_PER_REGISTRY_BUCKET`.
+    pub fn open_registry_bucket(ctx: Context<OpenRegistryBucket>) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        let bucket_index = registry.bucket_count;
+        if registry.current_bucket() != bucket_index {
+            return err!(DGEError::RegistryBucketNotNeeded);
+        }
+        registry.bucket_count = bucket_index.checked_add(1).ok_or(DGEError::ArithmeticOverflow)?;
+
+        let registry_bucket = &mut ctx.accounts.registry_bucket;
+        registry_bucket.bucket_index = bucket_index;
+        registry_bucket.grants = Vec::new();
+        registry_bucket.bump = ctx.bumps.registry_bucket;
+
+        msg!("Registry bucket {} opened.", bucket_index);
+        Ok(())
+    }
+
+    /// Creates the singleton VerifierSet PDA. Governance only.
+    /// The set starts with the given verifiers and M-of-N threshold.
+    pub fn initialize_verifier_set(
+        ctx: Context<InitializeVerifierSet>,
+        verifiers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        let verifier_set = &mut ctx.accounts.verifier_set;
+        verifier_set.verifiers = Vec::new();
+        for verifier in verifiers {
+            verifier_set.add(verifier)?;
+        }
+        verifier_set.set_threshold(threshold)?;
+        verifier_set.bump = ctx.bumps.verifier_set;
+
+        msg!("Verifier set initialized: {}-of-{}.", threshold, verifier_set.verifiers.len());
+        Ok(())
+    }
+
+    /// Registers a new metric verifier. Governance only.
+    pub fn add_verifier(ctx: Context<ConfigureVerifierSet>, verifier: Pubkey) -> Result<()> {
+        ctx.accounts.verifier_set.add(verifier)?;
+        msg!("Verifier {} added.", verifier);
+        Ok(())
+    }
+
+    /// Removes a metric verifier. Governance only. The threshold must already be
+    /// satisfiable by the remaining verifiers.
+    pub fn remove_verifier(ctx: Context<ConfigureVerifierSet>, verifier: Pubkey) -> Result<()> {
+        let verifier_set = &mut ctx.accounts.verifier_set;
+        let position = verifier_set
+            .verifiers
+            .iter()
+            .position(|existing| *existing == verifier)
+            .ok_or(DGEError::VerifierNotFound)?;
+        if verifier_set.verifiers.len() <= verifier_set.threshold as usize {
+            return err!(DGEError::InvalidVerifierThreshold);
+        }
+        verifier_set.verifiers.remove(position);
+
+        msg!("Verifier {} removed.", verifier);
+        Ok(())
+    }
+
+    /// Updates the number of verifier signatures required per metric submission. Governance only.
+    pub fn set_verifier_threshold(ctx: Context<ConfigureVerifierSet>, threshold: u8) -> Result<()> {
+        ctx.accounts.verifier_set.set_threshold(threshold)?;
+        msg!("Verifier threshold set to {}.", threshold);
+        Ok(())
+    }
+
+    /// Creates the singleton InsuranceFund PDA. Governance only.
+    /// The payout fee routed into the fund is set in `ProgramConfig`.
+    pub fn initialize_insurance_fund(ctx: Context<InitializeInsuranceFund>) -> Result<()> {
+        let insurance_fund = &mut ctx.accounts.insurance_fund;
+        insurance_fund.total_collected = 0;
+        insurance_fund.total_withdrawn = 0;
+        insurance_fund.bump = ctx.bumps.insurance_fund;
+        insurance_fund.outstanding_coverage = 0;
+
+        msg!("Insurance Fund initialized.");
+        Ok(())
+    }
+
+    /// Withdraws lamports from the InsuranceFund PDA, e.g. to compensate the victims
+    /// of a failed grant. Governance only; the fund always stays rent-exempt and keeps
+    /// enough to honour every open coverage policy in full.
+    pub fn withdraw_insurance_fund(ctx: Context<WithdrawInsuranceFund>, amount: u64) -> Result<()> {
+        let recipient_info = ctx.accounts.recipient.to_account_info();
+        let reserved = ctx.accounts.insurance_fund.outstanding_coverage;
+        pay_from_insurance_fund(&mut ctx.accounts.insurance_fund, &recipient_info, amount, reserved)?;
+
+        msg!("Insurance Fund withdrawal of {} lamports to {}.", amount, recipient_info.key());
+        Ok(())
+    }
+
+    /// Records a community co-funder's contribution to a grant (e.g. from a matching round),
+    /// entitling them to buy cover on it up to that amount. Governance only.
+    pub fn record_co_funding(ctx: Context<RecordCoFunding>, funder: Pubkey, contributed_amount: u64) -> Result<()> {
+        if contributed_amount == 0 {
+            return err!(DGEError::InvalidCoverageAmount);
+        }
+        let co_funding = &mut ctx.accounts.co_funding;
+        co_funding.grant = ctx.accounts.grant.key();
+        co_funding.funder = funder;
+        co_funding.contributed_amount = contributed_amount;
+        co_funding.bump = ctx.bumps.co_funding;
+
+        msg!("Co-funder {} recorded on grant {}: {} lamports.", funder, co_funding.grant, contributed_amount);
+        Ok(())
+    }
+
+    /// Buys insurance on a grant: if the grant is later liquidated, the holder can claim up to
+    /// `covered_amount` lamports from the InsuranceFund. The premium is priced at
+    /// `config.coverage_premium_bps` of the cover and paid into the fund up front.
+    ///
+    /// Only recorded co-funders can buy cover, each up to their contribution, and all cover on a
+    /// grant together is capped at its undisbursed amount. Cover cannot be bought once the grant
+    /// is liquidated, while a challenge is open, or after a milestone has missed its pass score.
+    pub fn purchase_coverage(ctx: Context<PurchaseCoverage>, covered_amount: u64) -> Result<()> {
+        let grant = &mut ctx.accounts.grant;
+        if grant.is_liquidated {
+            return err!(DGEError::GrantLiquidated);
+        }
+        if grant.is_frozen {
+            return err!(DGEError::GrantFrozen);
+        }
+        if grant.missed_pass_score {
+            return err!(DGEError::CoverageClosed);
+        }
+        let exposure = grant
+            .total_grant_amount
+            .checked_sub(grant.amount_disbursed)
+            .ok_or(DGEError::ArithmeticOverflow)?;
+        let total_covered = grant.total_covered.checked_add(covered_amount).ok_or(DGEError::ArithmeticOverflow)?;
+        if covered_amount == 0 || covered_amount > ctx.accounts.co_funding.contributed_amount || total_covered > exposure {
+            return err!(DGEError::InvalidCoverageAmount);
+        }
+        grant.total_covered = total_covered;
+
+        let premium = ctx.accounts.config.coverage_premium_for(covered_amount)?;
+        let insurance_fund = &mut ctx.accounts.insurance_fund;
+        insurance_fund.total_collected = insurance_fund
+            .total_collected
+            .checked_add(premium)
+            .ok_or(DGEError::ArithmeticOverflow)?;
+        insurance_fund.outstanding_coverage = insurance_fund
+            .outstanding_coverage
+            .checked_add(covered_amount)
+            .ok_or(DGEError::ArithmeticOverflow)?;
+
+        let coverage = &mut ctx.accounts.coverage;
+        coverage.grant = grant.key();
+        coverage.holder = ctx.accounts.holder.key();
+        coverage.covered_amount = covered_amount;
+        coverage.premium_paid = premium;
+        coverage.bump = ctx.bumps.coverage;
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.holder.to_account_info(),
+                    to: ctx.accounts.insurance_fund.to_account_info(),
+                },
+            ),
+            premium,
+        )?;
+
+        emit!(CoveragePurchased {
+            grant: coverage.grant,
+            holder: coverage.holder,
+            covered_amount,
+            premium,
+        });
+        Ok(())
+    }
+
+    /// Pays out a coverage policy once its grant has been liquidated (by a failed D-Metric,
+    /// governance, or an upheld challenge), then closes the policy to the holder.
+    ///
+    /// Policies share what the grant had left undisbursed at liquidation pro rata: if tranches
+    /// paid out after cover was bought, every policy is scaled down by the same factor.
+    pub fn claim_coverage(ctx: Context<ClaimCoverage>) -> Result<()> {
+        let grant = &ctx.accounts.grant;
+        if !grant.is_liquidated {
+            return err!(DGEError::GrantNotLiquidated);
+        }
+
+        let covered_amount = ctx.accounts.coverage.covered_amount;
+        let payout = grant.coverage_payout_for(covered_amount)?;
+        let insurance_fund = &mut ctx.accounts.insurance_fund;
+        insurance_fund.outstanding_coverage = insurance_fund.outstanding_coverage.saturating_sub(covered_amount);
+        let holder_info = ctx.accounts.holder.to_account_info();
+        pay_from_insurance_fund(insurance_fund, &holder_info, payout, 0)?;
+
+        emit!(CoverageClaimed {
+            grant: grant.key(),
+            holder: holder_info.key(),
+            covered_amount,
+            payout,
+        });
+        Ok(())
+    }
+
+    /// Closes a coverage policy on a grant that can no longer be liquidated (every tranche paid
+    /// and the last challenge window closed), releasing its reserve in the InsuranceFund.
+    /// Permissionless; the policy's rent goes back to its holder.
+    pub fn expire_coverage(ctx: Context<ExpireCoverage>) -> Result<()> {
+        let grant = &ctx.accounts.grant;
+        if grant.is_liquidated
+            || !grant.is_settled(ctx.accounts.config.challenge_window_seconds, Clock::get()?.unix_timestamp)?
+        {
+            return err!(DGEError::CoverageStillActive);
+        }
+
+        let covered_amount = ctx.accounts.coverage.covered_amount;
+        let insurance_fund = &mut ctx.accounts.insurance_fund;
+        insurance_fund.outstanding_coverage = insurance_fund.outstanding_coverage.saturating_sub(covered_amount);
+
+        emit!(CoverageExpired {
+            grant: grant.key(),
+            holder: ctx.accounts.holder.key(),
+            covered_amount,
+        });
+        Ok(())
+    }
+
+    /// Replaces the per-milestone pass scores of a grant, e.g. easing early milestones and
+    /// tightening later ones. Governance only. Already-paid milestones must keep their score.
+    pub fn set_milestone_schedule(ctx: Context<SetMilestoneSchedule>, required_scores: Vec<u8>) -> Result<()> {
+        let grant = &ctx.accounts.grant;
+        let milestone_schedule = &mut ctx.accounts.milestone_schedule;
+
+        let paid = grant.tranches_completed as usize;
+        if required_scores.len() != grant.total_tranches as usize
+            || required_scores.iter().any(|score| *score > MAX_D_METRIC_SCORE)
+            || required_scores[..paid] != milestone_schedule.required_scores[..paid]
+        {
+            return err!(DGEError::InvalidMilestoneSchedule);
+        }
+        milestone_schedule.required_scores = required_scores;
+
+        msg!("Milestone schedule updated: {:?}.", milestone_schedule.required_scores);
+        Ok(())
+    }
+
+    /// Escrows the full grant amount in the grant's vault PDA at approval. Governance only.
+    /// Sub-grants are funded by their umbrella grant instead (see `fund_sub_grant`).
+    /// Once funded, payouts no longer depend on treasury liquidity, and the builder has an
+    /// on-chain guarantee that every tranche is backed.
+    pub fn fund_grant(ctx: Context<FundGrant>) -> Result<()> {
+        let grant = &mut ctx.accounts.grant;
+        if grant.is_liquidated {
+            return err!(DGEError::GrantLiquidated);
+        }
+        // The vault is closed again by `refund_grant_vault`, so its `init` alone cannot stop a re-fund.
+        if grant.is_funded {
+            return err!(DGEError::DuplicateFunding);
+        }
+        // Past the deadline the builder may already be reclaiming the bond.
+        if Clock::get()?.unix_timestamp > grant.funding_deadline_ts {
+            return err!(DGEError::FundingDeadlinePassed);
+        }
+        grant.is_funded = true;
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.dao_authority.to_account_info(),
+                    to: ctx.accounts.grant_vault.to_account_info(),
+                },
+            ),
+            grant.total_grant_amount,
+        )?;
+
+        let grant_vault = &mut ctx.accounts.grant_vault;
+        grant_vault.grant = grant.key();
+        grant_vault.funded_amount = grant.total_grant_amount;
+        grant_vault.disbursed_amount = 0;
+        grant_vault.bump = ctx.bumps.grant_vault;
+
+        msg!("Grant {} funded: {} lamports escrowed.", grant_vault.grant, grant_vault.funded_amount);
+        Ok(())
+    }
+
+    /// Funds `sub_grant` out of an umbrella grant's escrow, making the umbrella's builder the
+    /// approver of the sub-grant's milestones. The umbrella builder signs instead of governance.
+    ///
+    /// The sub-grant is opened (and bonded) by its own builder with `initialize_grant`, and that
+    /// builder co-signs here to accept the umbrella as its parent. Its total counts as disbursed
+    /// from the umbrella, so all sub-grants together can never exceed what the umbrella has left.
+    /// Sub-grants cannot fund sub-grants of their own.
+    pub fn fund_sub_grant(ctx: Context<FundSubGrant>) -> Result<()> {
+        let umbrella = &mut ctx.accounts.umbrella;
+        let sub_grant = &mut ctx.accounts.sub_grant;
+        if umbrella.is_liquidated || sub_grant.is_liquidated {
+            return err!(DGEError::GrantLiquidated);
+        }
+        if umbrella.is_frozen {
+            return err!(DGEError::GrantFrozen);
+        }
+        if sub_grant.is_funded {
+            return err!(DGEError::DuplicateFunding);
+        }
+        // Only one level of nesting: the umbrella is no one's child, and a grant that already
+        // passes escrow on to children of its own cannot become one.
+        if umbrella.parent_grant != Pubkey::default() || sub_grant.sub_grant_allocated > 0 {
+            return err!(DGEError::InvalidSubGrant);
+        }
+        if Clock::get()?.unix_timestamp > sub_grant.funding_deadline_ts {
+            return err!(DGEError::FundingDeadlinePassed);
+        }
+
+        // --- AGGREGATE CAP ---
+        let amount = sub_grant.total_grant_amount;
+        let remaining = umbrella
+            .total_grant_amount
+            .checked_sub(umbrella.amount_disbursed)
+            .ok_or(DGEError::ArithmeticOverflow)?;
+        if amount > remaining {
+            return err!(DGEError::SubGrantCapExceeded);
+        }
+        umbrella.amount_disbursed = umbrella.amount_disbursed.checked_add(amount).ok_or(DGEError::ArithmeticOverflow)?;
+        umbrella.sub_grant_allocated = umbrella
+            .sub_grant_allocated
+            .checked_add(amount)
+            .ok_or(DGEError::ArithmeticOverflow)?;
+        sub_grant.parent_grant = umbrella.key();
+        sub_grant.is_funded = true;
+
+        let umbrella_vault = &mut ctx.accounts.umbrella_vault;
+        umbrella_vault.disbursed_amount = umbrella_vault
+            .disbursed_amount
+            .checked_add(amount)
+            .ok_or(DGEError::ArithmeticOverflow)?;
+        let sub_grant_vault = &mut ctx.accounts.sub_grant_vault;
+        sub_grant_vault.grant = sub_grant.key();
+        sub_grant_vault.funded_amount = amount;
+        sub_grant_vault.disbursed_amount = 0;
+        sub_grant_vault.bump = ctx.bumps.sub_grant_vault;
+
+        // Both vaults are owned by this program, so the escrow moves directly.
+        move_lamports(&umbrella_vault.to_account_info(), &sub_grant_vault.to_account_info(), amount)?;
+
+        emit!(SubGrantFunded {
+            umbrella: umbrella.key(),
+            sub_grant: sub_grant.key(),
+            amount,
+        });
+        msg!("Sub-grant {} funded with {} lamports from umbrella grant {}.", sub_grant.key(), amount, umbrella.key());
+        Ok(())
+    }
+
+    /// Closes a liquidated or fully settled grant's vault, returning undisbursed escrow (including
+    /// any partial-payout shortfall) and the vault's rent to the DAO treasury. Governance only.
+    ///
+    /// A sub-grant's escrow belongs to its umbrella and goes back with `refund_sub_grant_vault`.
+    /// Only once the umbrella's own vault is closed may it go to the treasury here; the umbrella
+    /// vault is then passed in `remaining_accounts` to show that it is gone.
+    pub fn refund_grant_vault(ctx: Context<RefundGrantVault>) -> Result<()> {
+        let grant = &ctx.accounts.grant;
+        if !grant.is_liquidated && grant.tranches_completed < grant.total_tranches {
+            return err!(DGEError::GrantNotLiquidated);
+        }
+        if grant.parent_grant != Pubkey::default() {
+            let (umbrella_vault, _) =
+                Pubkey::find_program_address(&[GRANT_VAULT_SEED, grant.parent_grant.as_ref()], &crate::ID);
+            let umbrella_vault_closed = ctx
+                .remaining_accounts
+                .iter()
+                .find(|account| account.key == &umbrella_vault)
+                .map(|account| account.lamports() == 0);
+            if umbrella_vault_closed != Some(true) {
+                return err!(DGEError::UmbrellaVaultOpen);
+            }
+        }
+
+        msg!(
+            "Grant vault closed. {} lamports of escrow returned to {}.",
+            ctx.accounts.grant_vault.remaining(),
+            ctx.accounts.dao_treasury.key()
+        );
+        Ok(())
+    }
+
+    /// Closes a liquidated or fully settled sub-grant's vault into its umbrella grant's vault.
+    /// The undisbursed escrow is credited back to the umbrella, so it can fund other sub-grants
+    /// or its own tranches again. Governance only.
+    pub fn refund_sub_grant_vault(ctx: Context<RefundSubGrantVault>) -> Result<()> {
+        let sub_grant = &ctx.accounts.sub_grant;
+        if !sub_grant.is_liquidated && sub_grant.tranches_completed < sub_grant.total_tranches {
+            return err!(DGEError::GrantNotLiquidated);
+        }
+
+        let returned = ctx.accounts.sub_grant_vault.remaining();
+        let umbrella = &mut ctx.accounts.umbrella;
+        umbrella.amount_disbursed = umbrella.amount_disbursed.checked_sub(returned).ok_or(DGEError::ArithmeticOverflow)?;
+        umbrella.sub_grant_allocated = umbrella
+            .sub_grant_allocated
+            .checked_sub(returned)
+            .ok_or(DGEError::ArithmeticOverflow)?;
+        let umbrella_vault = &mut ctx.accounts.umbrella_vault;
+        umbrella_vault.disbursed_amount = umbrella_vault
+            .disbursed_amount
+            .checked_sub(returned)
+            .ok_or(DGEError::ArithmeticOverflow)?;
+
+        msg!(
+            "Sub-grant vault closed. {} lamports of escrow returned to umbrella grant {}.",
+            returned,
+            umbrella.key()
+        );
+        Ok(())
+    }
+
+    /// Lets the builder walk away from a grant the DAO never funded: once `funding_deadline_ts`
+    /// has passed without the grant being funded, the Builder Bond is returned and the grant and
+    /// its milestone schedule are closed to the builder. Needs no governance signature.
+    ///
+    /// The grant's registry slot is cleared to `Pubkey::default()` and it no longer counts
+    /// towards the builder's `grants_started`.
+    pub fn reclaim_unfunded_grant(ctx: Context<ReclaimUnfundedGrant>) -> Result<()> {
+        let grant = &ctx.accounts.grant;
+        if grant.is_liquidated {
+            return err!(DGEError::GrantLiquidated);
+        }
+        if Clock::get()?.unix_timestamp <= grant.funding_deadline_ts {
+            return err!(DGEError::FundingDeadlineNotReached);
+        }
+        // Checked on the grant itself: the vault is gone again once `refund_grant_vault` closes it.
+        if grant.is_funded {
+            return err!(DGEError::GrantAlreadyFunded);
+        }
+
+        let registry_bucket = &mut ctx.accounts.registry_bucket;
+        let slot = (grant.registry_index % GRANTS_PER_REGISTRY_BUCKET as u64) as usize;
+        if registry_bucket.grants.get(slot) == Some(&grant.key()) {
+            registry_bucket.grants[slot] = Pubkey::default();
+        }
+        let builder_profile = &mut ctx.accounts.builder_profile;
+        builder_profile.grants_started = builder_profile
+            .grants_started
+            .checked_sub(1)
+            .ok_or(DGEError::ArithmeticOverflow)?;
+
+        let vault_authority_seeds: &[&[u8]] = &[BOND_VAULT_AUTHORITY_SEED, &[ctx.bumps.vault_authority]];
+        token::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.bond_vault.to_account_info(),
+                    mint: ctx.accounts.bond_mint.to_account_info(),
+                    to: ctx.accounts.builder_token_account.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                },
+                &[vault_authority_seeds],
+            ),
+            grant.builder_bond_amount,
+            ctx.accounts.bond_mint.decimals,
+        )?;
+
+        emit!(UnfundedGrantReclaimed {
+            grant: grant.key(),
+            builder: grant.builder,
+            bond_amount: grant.builder_bond_amount,
+        });
+        msg!("Unfunded grant closed. Builder Bond of {} returned to {}.", grant.builder_bond_amount, grant.builder);
+        Ok(())
+    }
+
+    /// Returns the Builder Bond of a completed grant to its builder. Every tranche must be paid,
+    /// no challenge may be open, and the last milestone's challenge window must have closed.
+    /// Needs no governance signature.
+    pub fn release_bond(ctx: Context<ReleaseBond>) -> Result<()> {
+        let grant = &mut ctx.accounts.grant;
+        if grant.is_liquidated {
+            return err!(DGEError::GrantLiquidated);
+        }
+        if grant.is_frozen {
+            return err!(DGEError::GrantFrozen);
+        }
+        let is_settled = grant.is_settled(ctx.accounts.config.challenge_window_seconds, Clock::get()?.unix_timestamp)?;
+        if !is_settled || grant.builder_bond_amount == 0 {
+            return err!(DGEError::BondLocked);
+        }
+
+        // The bond is settled before the CPI, so it can never be released twice.
+        let amount = grant.builder_bond_amount;
+        grant.builder_bond_amount = 0;
+        let vault_authority_seeds: &[&[u8]] = &[BOND_VAULT_AUTHORITY_SEED, &[ctx.bumps.vault_authority]];
+        token::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.bond_vault.to_account_info(),
+                    mint: ctx.accounts.bond_mint.to_account_info(),
+                    to: ctx.accounts.builder_token_account.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                },
+                &[vault_authority_seeds],
+            ),
+            amount,
+            ctx.accounts.bond_mint.decimals,
+        )?;
+
+        emit!(BondReleased {
+            grant: grant.key(),
+            builder: grant.builder,
+            bond_amount: amount,
+        });
+        msg!("Grant completed. Builder Bond of {} returned to {}.", amount, grant.builder);
+        Ok(())
+    }
+
+    /// Moves what is left of a liquidated grant's Builder Bond (after any challenger share) to a
+    /// DAO treasury token account. Governance only. Not while a challenge is open, since an
+    /// upheld challenge is still paid out of the bond.
+    pub fn sweep_liquidated_bond(ctx: Context<SweepLiquidatedBond>) -> Result<()> {
+        let grant = &mut ctx.accounts.grant;
+        if !grant.is_liquidated {
+            return err!(DGEError::GrantNotLiquidated);
+        }
+        if grant.is_frozen {
+            return err!(DGEError::GrantFrozen);
+        }
+        if grant.builder_bond_amount == 0 {
+            return err!(DGEError::BondLocked);
+        }
+
+        let amount = grant.builder_bond_amount;
+        grant.builder_bond_amount = 0;
+        let vault_authority_seeds: &[&[u8]] = &[BOND_VAULT_AUTHORITY_SEED, &[ctx.bumps.vault_authority]];
+        token::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.bond_vault.to_account_info(),
+                    mint: ctx.accounts.bond_mint.to_account_info(),
+                    to: ctx.accounts.dao_treasury_token_account.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                },
+                &[vault_authority_seeds],
+            ),
+            amount,
+            ctx.accounts.bond_mint.decimals,
+        )?;
+
+        emit!(LiquidatedBondSwept {
+            grant: grant.key(),
+            treasury: ctx.accounts.dao_treasury_token_account.key(),
+            bond_amount: amount,
+        });
+        msg!("Liquidated Builder Bond of {} swept to the DAO treasury.", amount);
+        Ok(())
+    }
+
+    /// Whitelists an SPL token as Builder Bond collateral and creates its vault. Governance only.
+    /// `haircut_bps` grosses up the bond for volatile collateral (10_000 = 100%, 13_000 = 130%).
+    /// `oracle` is the only key allowed to post the token's USD price; bonds cannot be posted in
+    /// the token until it has.
+    pub fn register_collateral(ctx: Context<RegisterCollateral>, haircut_bps: u16, oracle: Pubkey) -> Result<()> {
+        validate_collateral_haircut(haircut_bps)?;
+
+        let collateral = &mut ctx.accounts.collateral;
+        collateral.mint = ctx.accounts.mint.key();
+        collateral.vault = ctx.accounts.vault.key();
+        collateral.haircut_bps = haircut_bps;
+        collateral.is_enabled = true;
+        collateral.bump = ctx.bumps.collateral;
+        collateral.oracle = oracle;
+        collateral.price_in_usd_scaled = 0;
+        collateral.price_updated_at = 0;
+
+        msg!("Collateral {} registered with a {} bps haircut.", collateral.mint, haircut_bps);
+        Ok(())
+    }
+
+    /// Changes a collateral token's haircut or oracle, or stops accepting it for new bonds.
+    /// Governance only. Bonds already in the vault are unaffected.
+    pub fn update_collateral(ctx: Context<UpdateCollateral>, haircut_bps: u16, is_enabled: bool, oracle: Pubkey) -> Result<()> {
+        validate_collateral_haircut(haircut_bps)?;
+
+        let collateral = &mut ctx.accounts.collateral;
+        collateral.haircut_bps = haircut_bps;
+        collateral.is_enabled = is_enabled;
+        if collateral.oracle != oracle {
+            // A new oracle starts without a price; nothing the old one posted is trusted.
+            collateral.oracle = oracle;
+            collateral.price_in_usd_scaled = 0;
+            collateral.price_updated_at = 0;
+        }
+
+        msg!("Collateral {} updated: {} bps haircut, enabled: {}.", collateral.mint, haircut_bps, is_enabled);
+        Ok(())
+    }
+
+    /// Posts the USD price of one whole collateral token, scaled by 10^18. Only the collateral's
+    /// registered oracle may post, e.g. a relayer forwarding a Pyth or Switchboard feed.
+    pub fn update_collateral_price(ctx: Context<UpdateCollateralPrice>, price_in_usd_scaled: u128) -> Result<()> {
+        if price_in_usd_scaled == 0 {
+            return err!(DGEError::PriceUnavailable);
+        }
+
+        let collateral = &mut ctx.accounts.collateral;
+        collateral.price_in_usd_scaled = price_in_usd_scaled;
+        collateral.price_updated_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+}
+
+/// Haircuts below 100% would let a bond be worth less than its USD requirement.
+fn validate_collateral_haircut(haircut_bps: u16) -> Result<()> {
+    if (haircut_bps as u64) < HAIRCUT_BPS_DENOMINATOR || haircut_bps > MAX_COLLATERAL_HAIRCUT_BPS {
+        return err!(DGEError::InvalidCollateralHaircut);
+    }
+    Ok(())
+}
+
+/// Rejects cross-program invocations: the top-level instruction being executed, as recorded in
+/// the instructions sysvar, must be this program's own. Payouts and liquidations move funds and
+/// record liquidations, so no other program may wrap them in logic of its own.
+fn require_top_level_call(instructions_sysvar: &AccountInfo) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let current = load_instruction_at_checked(current_index as usize, instructions_sysvar)?;
+    if current.program_id != crate::ID {
+        return err!(DGEError::UnexpectedCpi);
+    }
+    Ok(())
+}
+
+/// Sub-grant milestones also need the umbrella grant's current builder, their approver, to sign.
+/// The umbrella Grant account and that signature are passed in `remaining_accounts`.
+fn require_umbrella_approval(umbrella: &Pubkey, accounts: &[AccountInfo]) -> Result<()> {
+    let umbrella_info = accounts
+        .iter()
+        .find(|account| account.key == umbrella && account.owner == &crate::ID)
+        .ok_or(DGEError::SubGrantNotApproved)?;
+    let umbrella_grant = Grant::try_deserialize(&mut &umbrella_info.try_borrow_data()?[..])?;
+    if !accounts.iter().any(|account| account.is_signer && account.key == &umbrella_grant.builder) {
+        return err!(DGEError::SubGrantNotApproved);
+    }
+    Ok(())
+}
+
+/// Moves lamports out of an account owned by this program. Credits need no ownership.
+/// Both sides are checked, so a short balance fails the instruction instead of wrapping.
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    let debited = from.lamports().checked_sub(amount).ok_or(DGEError::ArithmeticOverflow)?;
+    let credited = to.lamports().checked_add(amount).ok_or(DGEError::ArithmeticOverflow)?;
+    **from.try_borrow_mut_lamports()? = debited;
+    **to.try_borrow_mut_lamports()? = credited;
+    Ok(())
+}
+
+/// Pays `amount` lamports out of the InsuranceFund, keeping the fund rent-exempt.
+fn pay_from_insurance_fund(
+    insurance_fund: &mut Account<InsuranceFund>,
+    recipient: &AccountInfo,
+    amount: u64,
+    reserved: u64,
+) -> Result<()> {
+    let fund_info = insurance_fund.to_account_info();
+    let rent_minimum = Rent::get()?.minimum_balance(fund_info.data_len());
+    let available = fund_info.lamports().saturating_sub(rent_minimum).saturating_sub(reserved);
+    if amount > available {
+        return err!(DGEError::InsufficientInsuranceFunds);
+    }
+
+    // The fund is owned by this program, so lamports can be moved directly.
+    move_lamports(&fund_info, recipient, amount)?;
+    insurance_fund.total_withdrawn = insurance_fund
+        .total_withdrawn
+        .checked_add(amount)
+        .ok_or(DGEError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+// --- PROGRAM CONSTANTS ---
+
+/// Seed for the singleton ProgramConfig PDA.
+pub const CONFIG_SEED: &[u8] = b"config";
+
+/// Seed prefix for Grant PDAs: `[GRANT_SEED, origin_builder, grant_nonce (u64 LE)]`.
+pub const GRANT_SEED: &[u8] = b"grant";
+
+/// Seed prefix for Challenge PDAs: `[CHALLENGE_SEED, grant, milestone_index]`.
+pub const CHALLENGE_SEED: &[u8] = b"challenge";
+
+/// Seed prefix for BuilderProfile PDAs: `[BUILDER_PROFILE_SEED, builder]`.
+pub const BUILDER_PROFILE_SEED: &[u8] = b"builder_profile";
+
+/// Seed for the singleton grant Registry PDA.
+pub const REGISTRY_SEED: &[u8] = b"registry";
+
+/// Seed prefix for registry bucket PDAs: `[REGISTRY_BUCKET_SEED, bucket_index (u64 LE)]`.
+pub const REGISTRY_BUCKET_SEED: &[u8] = b"registry_bucket";
+
+/// Grant pubkeys stored per registry bucket.
+pub const GRANTS_PER_REGISTRY_BUCKET: usize = 128;
+
+/// Seed for the singleton VerifierSet PDA.
+pub const VERIFIER_SET_SEED: &[u8] = b"verifier_set";
+
+/// Maximum number of registered metric verifiers.
+pub const MAX_VERIFIERS: usize = 10;
+
+/// Seed for the singleton InsuranceFund PDA.
+pub const INSURANCE_FUND_SEED: &[u8] = b"insurance_fund";
+
+/// Upper bound on the insurance fee (10%), protecting builders from a runaway configuration.
+pub const MAX_INSURANCE_FEE_BPS: u16 = 1_000;
+
+/// Basis-point denominator (10_000 bps = 100%).
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Default milestone challenge window (3 days).
+pub const DEFAULT_CHALLENGE_WINDOW_SECONDS: i64 = 3 * 24 * 60 * 60;
+
+/// Default minimum challenge stake (1 SOL), deterring frivolous disputes.
+pub const DEFAULT_MIN_CHALLENGE_STAKE: u64 = 1_000_000_000;
+
+/// Seed prefix for CollateralConfig PDAs: `[COLLATERAL_SEED, mint]`.
+pub const COLLATERAL_SEED: &[u8] = b"collateral";
+
+/// Seed prefix for MilestoneSchedule PDAs: `[MILESTONE_SCHEDULE_SEED, grant]`.
+pub const MILESTONE_SCHEDULE_SEED: &[u8] = b"milestone_schedule";
+
+/// Maximum number of milestone tranches per grant.
+pub const MAX_MILESTONES: usize = 16;
+
+/// Seed prefix for GrantVault PDAs: `[GRANT_VAULT_SEED, grant]`.
+pub const GRANT_VAULT_SEED: &[u8] = b"grant_vault";
+
+/// Seed for the PDA that owns every bond vault token account.
+pub const BOND_VAULT_AUTHORITY_SEED: &[u8] = b"bond_vault_authority";
+
+/// Upper bound on a collateral haircut (300%), keeping bonds in volatile tokens postable.
+pub const MAX_COLLATERAL_HAIRCUT_BPS: u16 = 30_000;
+
+/// Seed prefix for Coverage PDAs: `[COVERAGE_SEED, grant, holder]`.
+pub const COVERAGE_SEED: &[u8] = b"coverage";
+
+/// Seed prefix for CoFunding PDAs: `[CO_FUNDING_SEED, grant, funder]`.
+pub const CO_FUNDING_SEED: &[u8] = b"co_funding";
+
+/// Seed prefix for TrancheReceipt PDAs: `[TRANCHE_RECEIPT_SEED, grant, milestone_index]`.
+pub const TRANCHE_RECEIPT_SEED: &[u8] = b"tranche_receipt";
+
+/// Default coverage premium (5% of the covered amount).
+pub const DEFAULT_COVERAGE_PREMIUM_BPS: u16 = 500;
+
+/// Default Builder Bond escalation: +50% per prior liquidation on the builder's record.
+pub const DEFAULT_BOND_ESCALATION_BPS: u16 = 5_000;
+
+/// Default cap on the escalated Builder Bond (300% of the base requirement).
+pub const DEFAULT_MAX_BOND_MULTIPLIER_BPS: u16 = 30_000;
+
+/// Default share of the Builder Bond paid to the challenger of an upheld challenge (20%).
+pub const DEFAULT_CHALLENGER_BOND_SHARE_BPS: u16 = 2_000;
+
+/// Default age after which a collateral price can no longer price a bond (5 minutes).
+pub const DEFAULT_MAX_PRICE_AGE_SECONDS: i64 = 5 * 60;
+
+/// How far ahead of its creation a grant may be scheduled to start (one year).
+pub const MAX_GRANT_START_DELAY_SECONDS: i64 = 365 * 24 * 60 * 60;
+
+
+// --- ACCOUNTS & DATA STRUCTURES ---
+
+/// Context for initializing a new grant.
+#[derive(Accounts)]
+pub struct InitializeGrant<'info> {
+    /// CHECK: The builder is the one who pays for the account creation and stakes the bond.
+    #[account(mut)]
+    pub builder: Signer<'info>,
+    #[account(mut, seeds = [BUILDER_PROFILE_SEED, builder.key().as_ref()], bump = builder_profile.bump)]
+    pub builder_profile: Account<'info, BuilderProfile>,
+    /// Derived from the builder and the profile's next grant nonce, so each builder can hold
+    /// any number of grants at predictable addresses.
+    #[account(
+        init,
+        payer = builder,
+        space = 8 + Grant::LEN,
+        seeds = [GRANT_SEED, builder.key().as_ref(), &builder_profile.grant_nonce.to_le_bytes()],
+        bump,
+    )]
+    pub grant: Account<'info, Grant>,
+    #[account(
+        init,
+        payer = builder,
+        space = 8 + MilestoneSchedule::LEN,
+        seeds = [MILESTONE_SCHEDULE_SEED, grant.key().as_ref()],
+        bump,
+    )]
+    pub milestone_schedule: Box<Account<'info, MilestoneSchedule>>,
+    #[account(mut, seeds = [REGISTRY_SEED], bump = registry.bump)]
+    pub registry: Account<'info, Registry>,
+    /// The bucket the new grant is appended to, derived from the current grant count.
+    #[account(
+        mut,
+        seeds = [REGISTRY_BUCKET_SEED, &registry.current_bucket().to_le_bytes()],
+        bump = registry_bucket.bump,
+    )]
+    pub registry_bucket: Account<'info, RegistryBucket>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    pub bond_mint: Account<'info, Mint>,
+    #[account(seeds = [COLLATERAL_SEED, bond_mint.key().as_ref()], bump = collateral.bump)]
+    pub collateral: Account<'info, CollateralConfig>,
+    #[account(mut, token::mint = bond_mint, token::authority = builder)]
+    pub builder_token_account: Account<'info, TokenAccount>,
+    #[account(mut, address = collateral.vault)]
+    pub bond_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for creating the ProgramConfig PDA.
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(init, payer = upgrade_authority, space = 8 + ProgramConfig::LEN, seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut)]
+    pub upgrade_authority: Signer<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ DGEError::Unauthorized)]
+    pub program: Program<'info, program::DepthProtocolGrantEngine>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(upgrade_authority.key()) @ DGEError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for governance updates to the ProgramConfig.
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(mut, has_one = governance_authority @ DGEError::Unauthorized, seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    pub governance_authority: Signer<'info>,
+}
+
+/// Context for creating a builder's reputation record.
+#[derive(Accounts)]
+pub struct InitializeBuilderProfile<'info> {
+    #[account(
+        init,
+        payer = builder,
+        space = 8 + BuilderProfile::LEN,
+        seeds = [BUILDER_PROFILE_SEED, builder.key().as_ref()],
+        bump,
+    )]
+    pub builder_profile: Account<'info, BuilderProfile>,
+    #[account(mut)]
+    pub builder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for creating the grant Registry and its first bucket.
+#[derive(Accounts)]
+pub struct InitializeRegistry<'info> {
+    #[account(init, payer = payer, space = 8 + Registry::LEN, seeds = [REGISTRY_SEED], bump)]
+    pub registry: Account<'info, Registry>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RegistryBucket::LEN,
+        seeds = [REGISTRY_BUCKET_SEED, &0u64.to_le_bytes()],
+        bump,
+    )]
+    pub registry_bucket: Account<'info, RegistryBucket>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for opening the next registry bucket.
+#[derive(Accounts)]
+pub struct OpenRegistryBucket<'info> {
+    #[account(mut, seeds = [REGISTRY_SEED], bump = registry.bump)]
+    pub registry: Account<'info, Registry>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RegistryBucket::LEN,
+        seeds = [REGISTRY_BUCKET_SEED, &registry.bucket_count.to_le_bytes()],
+        bump,
+    )]
+    pub registry_bucket: Account<'info, RegistryBucket>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for milestone completion and payout.
+#[derive(Accounts)]
+#[instruction(milestone_index: u8)]
+pub struct MilestonePayout<'info> {
+    #[account(mut, has_one = builder, seeds = [GRANT_SEED, grant.origin_builder.as_ref(), &grant.grant_nonce.to_le_bytes()], bump = grant.bump)]
+    pub grant: Account<'info, Grant>,
+    /// Receives the tranche, net of the insurance fee.
+    #[account(mut)]
+    pub builder: Signer<'info>,
+    #[account(mut, seeds = [GRANT_VAULT_SEED, grant.key().as_ref()], bump = grant_vault.bump)]
+    pub grant_vault: Account<'info, GrantVault>,
+    #[account(has_one = grant, seeds = [MILESTONE_SCHEDULE_SEED, grant.key().as_ref()], bump = milestone_schedule.bump)]
+    pub milestone_schedule: Box<Account<'info, MilestoneSchedule>>,
+    #[account(mut, seeds = [INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    #[account(mut, address = grant.builder_profile)]
+    pub builder_profile: Account<'info, BuilderProfile>,
+    #[account(seeds = [VERIFIER_SET_SEED], bump = verifier_set.bump)]
+    pub verifier_set: Account<'info, VerifierSet>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        init,
+        payer = builder,
+        space = 8 + TrancheReceipt::LEN,
+        seeds = [TRANCHE_RECEIPT_SEED, grant.key().as_ref(), &[milestone_index]],
+        bump,
+    )]
+    pub receipt: Box<Account<'info, TrancheReceipt>>,
+    /// CHECK: The instructions sysvar, read to reject cross-program invocations.
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for governance changes to a grant's milestone pass scores.
+#[derive(Accounts)]
+pub struct SetMilestoneSchedule<'info> {
+    #[account(seeds = [GRANT_SEED, grant.origin_builder.as_ref(), &grant.grant_nonce.to_le_bytes()], bump = grant.bump)]
+    pub grant: Account<'info, Grant>,
+    #[account(mut, has_one = grant, seeds = [MILESTONE_SCHEDULE_SEED, grant.key().as_ref()], bump = milestone_schedule.bump)]
+    pub milestone_schedule: Account<'info, MilestoneSchedule>,
+    #[account(address = config.governance_authority @ DGEError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+/// Context for escrowing a grant's funding.
+#[derive(Accounts)]
+pub struct FundGrant<'info> {
+    #[account(mut, seeds = [GRANT_SEED, grant.origin_builder.as_ref(), &grant.grant_nonce.to_le_bytes()], bump = grant.bump)]
+    pub grant: Account<'info, Grant>,
+    #[account(
+        init,
+        payer = dao_authority,
+        space = 8 + GrantVault::LEN,
+        seeds = [GRANT_VAULT_SEED, grant.key().as_ref()],
+        bump,
+    )]
+    pub grant_vault: Account<'info, GrantVault>,
+    /// The governance authority recorded in the ProgramConfig; funds the escrow from the treasury.
+    #[account(mut, address = config.governance_authority @ DGEError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for funding a sub-grant out of an umbrella grant's escrow.
+#[derive(Accounts)]
+pub struct FundSubGrant<'info> {
+    #[account(
+        mut,
+        has_one = builder,
+        seeds = [GRANT_SEED, umbrella.origin_builder.as_ref(), &umbrella.grant_nonce.to_le_bytes()],
+        bump = umbrella.bump,
+    )]
+    pub umbrella: Box<Account<'info, Grant>>,
+    #[account(mut, seeds = [GRANT_VAULT_SEED, umbrella.key().as_ref()], bump = umbrella_vault.bump)]
+    pub umbrella_vault: Account<'info, GrantVault>,
+    #[account(
+        mut,
+        seeds = [GRANT_SEED, sub_grant.origin_builder.as_ref(), &sub_grant.grant_nonce.to_le_bytes()],
+        bump = sub_grant.bump,
+    )]
+    pub sub_grant: Box<Account<'info, Grant>>,
+    #[account(
+        init,
+        payer = builder,
+        space = 8 + GrantVault::LEN,
+        seeds = [GRANT_VAULT_SEED, sub_grant.key().as_ref()],
+        bump,
+    )]
+    pub sub_grant_vault: Account<'info, GrantVault>,
+    /// The umbrella grant's builder, approving the sub-grant; pays the sub-grant vault's rent.
+    #[account(mut)]
+    pub builder: Signer<'info>,
+    /// The sub-grant's builder, consenting to be funded (and approved) by the umbrella.
+    #[account(address = sub_grant.builder @ DGEError::Unauthorized)]
+    pub sub_grant_builder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for returning a liquidated grant's escrow to the treasury.
+#[derive(Accounts)]
+pub struct RefundGrantVault<'info> {
+    #[account(seeds = [GRANT_SEED, grant.origin_builder.as_ref(), &grant.grant_nonce.to_le_bytes()], bump = grant.bump)]
+    pub grant: Account<'info, Grant>,
+    #[account(
+        mut,
+        close = dao_treasury,
+        has_one = grant,
+        seeds = [GRANT_VAULT_SEED, grant.key().as_ref()],
+        bump = grant_vault.bump,
+    )]
+    pub grant_vault: Account<'info, GrantVault>,
+    /// CHECK: Lamport recipient chosen by governance (the DAO treasury).
+    #[account(mut)]
+    pub dao_treasury: UncheckedAccount<'info>,
+    #[account(address = config.governance_authority @ DGEError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+/// Context for returning a sub-grant's escrow to its umbrella grant.
+#[derive(Accounts)]
+pub struct RefundSubGrantVault<'info> {
+    #[account(
+        constraint = sub_grant.parent_grant == umbrella.key() @ DGEError::InvalidSubGrant,
+        seeds = [GRANT_SEED, sub_grant.origin_builder.as_ref(), &sub_grant.grant_nonce.to_le_bytes()],
+        bump = sub_grant.bump,
+    )]
+    pub sub_grant: Box<Account<'info, Grant>>,
+    #[account(
+        mut,
+        close = umbrella_vault,
+        constraint = sub_grant_vault.grant == sub_grant.key(),
+        seeds = [GRANT_VAULT_SEED, sub_grant.key().as_ref()],
+        bump = sub_grant_vault.bump,
+    )]
+    pub sub_grant_vault: Account<'info, GrantVault>,
+    #[account(
+        mut,
+        seeds = [GRANT_SEED, umbrella.origin_builder.as_ref(), &umbrella.grant_nonce.to_le_bytes()],
+        bump = umbrella.bump,
+    )]
+    pub umbrella: Box<Account<'info, Grant>>,
+    #[account(mut, seeds = [GRANT_VAULT_SEED, umbrella.key().as_ref()], bump = umbrella_vault.bump)]
+    pub umbrella_vault: Account<'info, GrantVault>,
+    #[account(address = config.governance_authority @ DGEError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+/// Context for a builder reclaiming the bond of a grant that was never funded.
+#[derive(Accounts)]
+pub struct ReclaimUnfundedGrant<'info> {
+    #[account(mut)]
+    pub builder: Signer<'info>,
+    #[account(
+        mut,
+        close = builder,
+        has_one = builder,
+        has_one = bond_mint,
+        seeds = [GRANT_SEED, grant.origin_builder.as_ref(), &grant.grant_nonce.to_le_bytes()],
+        bump = grant.bump,
+    )]
+    pub grant: Account<'info, Grant>,
+    #[account(
+        mut,
+        close = builder,
+        has_one = grant,
+        seeds = [MILESTONE_SCHEDULE_SEED, grant.key().as_ref()],
+        bump = milestone_schedule.bump,
+    )]
+    pub milestone_schedule: Box<Account<'info, MilestoneSchedule>>,
+    #[account(
+        mut,
+        seeds = [REGISTRY_BUCKET_SEED, &grant.registry_bucket_index().to_le_bytes()],
+        bump = registry_bucket.bump,
+    )]
+    pub registry_bucket: Box<Account<'info, RegistryBucket>>,
+    #[account(mut, address = grant.builder_profile)]
+    pub builder_profile: Account<'info, BuilderProfile>,
+    pub bond_mint: Account<'info, Mint>,
+    #[account(seeds = [COLLATERAL_SEED, bond_mint.key().as_ref()], bump = collateral.bump)]
+    pub collateral: Account<'info, CollateralConfig>,
+    #[account(mut, address = collateral.vault)]
+    pub bond_vault: Account<'info, TokenAccount>,
+    /// CHECK: PDA signer for bond vaults; holds no data.
+    #[account(seeds = [BOND_VAULT_AUTHORITY_SEED], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+    #[account(mut, token::mint = bond_mint, token::authority = builder)]
+    pub builder_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for returning a completed grant's bond to its builder.
+#[derive(Accounts)]
+pub struct ReleaseBond<'info> {
+    pub builder: Signer<'info>,
+    #[account(
+        mut,
+        has_one = builder,
+        has_one = bond_mint,
+        seeds = [GRANT_SEED, grant.origin_builder.as_ref(), &grant.grant_nonce.to_le_bytes()],
+        bump = grant.bump,
+    )]
+    pub grant: Account<'info, Grant>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    pub bond_mint: Account<'info, Mint>,
+    #[account(seeds = [COLLATERAL_SEED, bond_mint.key().as_ref()], bump = collateral.bump)]
+    pub collateral: Account<'info, CollateralConfig>,
+    #[account(mut, address = collateral.vault)]
+    pub bond_vault: Account<'info, TokenAccount>,
+    /// CHECK: PDA signer for bond vaults; holds no data.
+    #[account(seeds = [BOND_VAULT_AUTHORITY_SEED], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+    #[account(mut, token::mint = bond_mint, token::authority = builder)]
+    pub builder_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for sweeping a liquidated grant's bond to the DAO treasury.
+#[derive(Accounts)]
+pub struct SweepLiquidatedBond<'info> {
+    #[account(
+        mut,
+        has_one = bond_mint,
+        seeds = [GRANT_SEED, grant.origin_builder.as_ref(), &grant.grant_nonce.to_le_bytes()],
+        bump = grant.bump,
+    )]
+    pub grant: Account<'info, Grant>,
+    #[account(address = config.governance_authority @ DGEError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    pub bond_mint: Account<'info, Mint>,
+    #[account(seeds = [COLLATERAL_SEED, bond_mint.key().as_ref()], bump = collateral.bump)]
+    pub collateral: Account<'info, CollateralConfig>,
+    #[account(mut, address = collateral.vault)]
+    pub bond_vault: Account<'info, TokenAccount>,
+    /// CHECK: PDA signer for bond vaults; holds no data.
+    #[account(seeds = [BOND_VAULT_AUTHORITY_SEED], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+    /// Any token account of the bond mint chosen by governance (e.g., the DAO treasury's).
+    #[account(mut, token::mint = bond_mint)]
+    pub dao_treasury_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for bond liquidation (emergency fallback).
+#[derive(Accounts)]
+pub struct LiquidateBond<'info> {
+    #[account(mut, seeds = [GRANT_SEED, grant.origin_builder.as_ref(), &grant.grant_nonce.to_le_bytes()], bump = grant.bump)]
+    pub grant: Account<'info, Grant>,
+    /// The governance authority recorded in the ProgramConfig (DAO multisig/governance program).
+    #[account(address = config.governance_authority @ DGEError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+    #[account(mut, address = grant.builder_profile)]
+    pub builder_profile: Account<'info, BuilderProfile>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    /// CHECK: The instructions sysvar, read to reject cross-program invocations.
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Context for challenging a paid milestone.
+#[derive(Accounts)]
+#[instruction(milestone_index: u8)]
+pub struct ChallengeMilestone<'info> {
+    #[account(mut, seeds = [GRANT_SEED, grant.origin_builder.as_ref(), &grant.grant_nonce.to_le_bytes()], bump = grant.bump)]
+    pub grant: Account<'info, Grant>,
+    #[account(
+        init,
+        payer = challenger,
+        space = 8 + Challenge::LEN,
+        seeds = [CHALLENGE_SEED, grant.key().as_ref(), &[milestone_index]],
+        bump,
+    )]
+    pub challenge: Account<'info, Challenge>,
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for the DAO resolving a milestone challenge.
+#[derive(Accounts)]
+pub struct ResolveChallenge<'info> {
+    #[account(mut, seeds = [GRANT_SEED, grant.origin_builder.as_ref(), &grant.grant_nonce.to_le_bytes()], bump = grant.bump)]
+    pub grant: Account<'info, Grant>,
+    #[account(
+        mut,
+        close = challenger,
+        has_one = grant,
+        has_one = challenger,
+        seeds = [CHALLENGE_SEED, grant.key().as_ref(), &[challenge.milestone_index]],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, Challenge>,
+    /// CHECK: Receives the closed Challenge account's rent (and stake, if upheld); matched via `has_one`.
+    #[account(mut)]
+    pub challenger: UncheckedAccount<'info>,
+    /// CHECK: Receives the stake if the challenge is rejected; must be the grant's builder.
+    #[account(mut, address = grant.builder)]
+    pub builder: UncheckedAccount<'info>,
+    #[account(mut, address = grant.builder_profile)]
+    pub builder_profile: Account<'info, BuilderProfile>,
+    #[account(address = config.governance_authority @ DGEError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    /// Receipt of the disputed milestone; revoked if the challenge is upheld.
+    #[account(
+        mut,
+        has_one = grant,
+        seeds = [TRANCHE_RECEIPT_SEED, grant.key().as_ref(), &[challenge.milestone_index]],
+        bump = receipt.bump,
+    )]
+    pub receipt: Box<Account<'info, TrancheReceipt>>,
+    #[account(address = grant.bond_mint)]
+    pub bond_mint: Box<Account<'info, Mint>>,
+    #[account(seeds = [COLLATERAL_SEED, bond_mint.key().as_ref()], bump = collateral.bump)]
+    pub collateral: Box<Account<'info, CollateralConfig>>,
+    #[account(mut, address = collateral.vault)]
+    pub bond_vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: PDA signer for bond vaults; holds no data.
+    #[account(seeds = [BOND_VAULT_AUTHORITY_SEED], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+    /// Receives the challenger's share of the bond if the challenge is upheld.
+    #[account(mut, token::mint = bond_mint, token::authority = challenger)]
+    pub challenger_token_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for rotating a grant's builder authority.
+#[derive(Accounts)]
+pub struct RotateBuilderAuthority<'info> {
+    #[account(mut, has_one = builder, seeds = [GRANT_SEED, grant.origin_builder.as_ref(), &grant.grant_nonce.to_le_bytes()], bump = grant.bump)]
+    pub grant: Account<'info, Grant>,
+    /// The current builder key; must co-sign the rotation.
+    pub builder: Signer<'info>,
+    /// The governance authority recorded in the ProgramConfig (DAO multisig/governance program).
+    #[account(address = config.governance_authority @ DGEError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+/// Context for the CPI standing check. Takes no signers and mutates nothing,
+/// so any program can invoke it on behalf of its own users.
+#[derive(Accounts)]
+pub struct VerifyGrantStanding<'info> {
+    #[account(has_one = builder, seeds = [GRANT_SEED, grant.origin_builder.as_ref(), &grant.grant_nonce.to_le_bytes()], bump = grant.bump)]
+    pub grant: Account<'info, Grant>,
+    /// CHECK: Only compared against `grant.builder` via `has_one`; never read or written.
+    pub builder: UncheckedAccount<'info>,
+}
+
+/// Context for creating the VerifierSet PDA.
+#[derive(Accounts)]
+pub struct InitializeVerifierSet<'info> {
+    #[account(init, payer = authority, space = 8 + VerifierSet::LEN, seeds = [VERIFIER_SET_SEED], bump)]
+    pub verifier_set: Account<'info, VerifierSet>,
+    #[account(mut, address = config.governance_authority @ DGEError::Unauthorized)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for governance changes to the verifier set.
+#[derive(Accounts)]
+pub struct ConfigureVerifierSet<'info> {
+    #[account(mut, seeds = [VERIFIER_SET_SEED], bump = verifier_set.bump)]
+    pub verifier_set: Account<'info, VerifierSet>,
+    #[account(address = config.governance_authority @ DGEError::Unauthorized)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+/// Context for creating the InsuranceFund PDA.
+#[derive(Accounts)]
+pub struct InitializeInsuranceFund<'info> {
+    #[account(init, payer = authority, space = 8 + InsuranceFund::LEN, seeds = [INSURANCE_FUND_SEED], bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    #[account(mut, address = config.governance_authority @ DGEError::Unauthorized)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for governance withdrawals from the insurance fund.
+#[derive(Accounts)]
+pub struct WithdrawInsuranceFund<'info> {
+    #[account(mut, seeds = [INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    #[account(address = config.governance_authority @ DGEError::Unauthorized)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    /// CHECK: Arbitrary lamport recipient chosen by governance (e.g., a compensation multisig).
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+/// Context for recording a co-funder's contribution to a grant.
+#[derive(Accounts)]
+#[instruction(funder: Pubkey)]
+pub struct RecordCoFunding<'info> {
+    #[account(seeds = [GRANT_SEED, grant.origin_builder.as_ref(), &grant.grant_nonce.to_le_bytes()], bump = grant.bump)]
+    pub grant: Account<'info, Grant>,
+    #[account(
+        init,
+        payer = dao_authority,
+        space = 8 + CoFunding::LEN,
+        seeds = [CO_FUNDING_SEED, grant.key().as_ref(), funder.as_ref()],
+        bump,
+    )]
+    pub co_funding: Account<'info, CoFunding>,
+    #[account(mut, address = config.governance_authority @ DGEError::Unauthorized)]
+    pub dao_authority: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for buying insurance cover on a grant.
+#[derive(Accounts)]
+pub struct PurchaseCoverage<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    #[account(mut, seeds = [GRANT_SEED, grant.origin_builder.as_ref(), &grant.grant_nonce.to_le_bytes()], bump = grant.bump)]
+    pub grant: Account<'info, Grant>,
+    /// Only recorded co-funders of the grant can buy cover on it.
+    #[account(
+        has_one = grant,
+        seeds = [CO_FUNDING_SEED, grant.key().as_ref(), holder.key().as_ref()],
+        bump = co_funding.bump,
+    )]
+    pub co_funding: Account<'info, CoFunding>,
+    /// One policy per holder and grant.
+    #[account(
+        init,
+        payer = holder,
+        space = 8 + Coverage::LEN,
+        seeds = [COVERAGE_SEED, grant.key().as_ref(), holder.key().as_ref()],
+        bump,
+    )]
+    pub coverage: Account<'info, Coverage>,
+    #[account(mut, seeds = [INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for claiming a coverage policy after liquidation.
+#[derive(Accounts)]
+pub struct ClaimCoverage<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    #[account(seeds = [GRANT_SEED, grant.origin_builder.as_ref(), &grant.grant_nonce.to_le_bytes()], bump = grant.bump)]
+    pub grant: Account<'info, Grant>,
+    #[account(
+        mut,
+        close = holder,
+        has_one = grant,
+        has_one = holder,
+        seeds = [COVERAGE_SEED, grant.key().as_ref(), holder.key().as_ref()],
+        bump = coverage.bump,
+    )]
+    pub coverage: Account<'info, Coverage>,
+    #[account(mut, seeds = [INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+}
+
+/// Context for closing a coverage policy on a settled grant.
+#[derive(Accounts)]
+pub struct ExpireCoverage<'info> {
+    /// CHECK: Receives the policy's rent; only matched against `coverage.holder`.
+    #[account(mut)]
+    pub holder: UncheckedAccount<'info>,
+    #[account(seeds = [GRANT_SEED, grant.origin_builder.as_ref(), &grant.grant_nonce.to_le_bytes()], bump = grant.bump)]
+    pub grant: Account<'info, Grant>,
+    #[account(
+        mut,
+        close = holder,
+        has_one = grant,
+        has_one = holder,
+        seeds = [COVERAGE_SEED, grant.key().as_ref(), holder.key().as_ref()],
+        bump = coverage.bump,
+    )]
+    pub coverage: Account<'info, Coverage>,
+    #[account(mut, seeds = [INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+/// Context for whitelisting a bond collateral token.
+#[derive(Accounts)]
+pub struct RegisterCollateral<'info> {
+    #[account(init, payer = authority, space = 8 + CollateralConfig::LEN, seeds = [COLLATERAL_SEED, mint.key().as_ref()], bump)]
+    pub collateral: Account<'info, CollateralConfig>,
+    pub mint: Account<'info, Mint>,
+    /// The bond vault for this mint: the associated token account of the vault authority PDA.
+    #[account(init, payer = authority, associated_token::mint = mint, associated_token::authority = vault_authority)]
+    pub vault: Account<'info, TokenAccount>,
+    /// CHECK: PDA signer for bond vaults; holds no data.
+    #[account(seeds = [BOND_VAULT_AUTHORITY_SEED], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+    #[account(mut, address = config.governance_authority @ DGEError::Unauthorized)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for governance changes to a collateral token.
+#[derive(Accounts)]
+pub struct UpdateCollateral<'info> {
+    #[account(mut, seeds = [COLLATERAL_SEED, collateral.mint.as_ref()], bump = collateral.bump)]
+    pub collateral: Account<'info, CollateralConfig>,
+    #[account(address = config.governance_authority @ DGEError::Unauthorized)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+/// Context for a collateral oracle posting a price.
+#[derive(Accounts)]
+pub struct UpdateCollateralPrice<'info> {
+    #[account(mut, seeds = [COLLATERAL_SEED, collateral.mint.as_ref()], bump = collateral.bump)]
+    pub collateral: Account<'info, CollateralConfig>,
+    #[account(address = collateral.oracle @ DGEError::Unauthorized)]
+    pub oracle: Signer<'info>,
+}
+
+/// The main Grant Account data structure.
+#[account]
+pub struct Grant {
+    pub builder: Pubkey,            // 32
+    pub total_grant_amount: u64,    // 8
+    pub builder_bond_amount: u64,   // 8
+    pub tranches_completed: u8,     // 1
+    pub total_tranches: u8,         // 1
+    pub is_liquidated: bool,        // 1
+    pub tier: u8,                   // 1
+    pub registry_index: u64,        // 8
+    pub builder_profile: Pubkey,    // 32
+    pub last_payout_ts: i64,        // 8
+    pub is_frozen: bool,            // 1
+    pub origin_builder: Pubkey,     // 32 (creator key; fixed across rotation so the PDA stays derivable)
+    pub grant_nonce: u64,           // 8
+    pub bump: u8,                   // 1
+    pub bond_mint: Pubkey,          // 32 (collateral the Builder Bond is held in)
+    pub amount_disbursed: u64,      // 8 (gross of the insurance fee)
+    pub start_ts: i64,              // 8 (milestones cannot be claimed earlier)
+    pub funding_deadline_ts: i64,   // 8 (after which an unfunded grant can be reclaimed)
+    pub parent_grant: Pubkey,       // 32 (umbrella grant that funded this sub-grant, or default)
+    pub sub_grant_allocated: u64,   // 8 (escrow passed on to sub-grants; part of amount_disbursed)
+    pub is_funded: bool,            // 1 (escrow was ever placed in the grant vault)
+    pub total_covered: u64,         // 8 (sum of all coverage policies on this grant)
+    pub missed_pass_score: bool,    // 1 (a milestone paid out short of its pass score)
+    // Padding to ensure future expansion: ~30 bytes
+}
+
+impl Grant {
+    pub const LEN: usize = 32 + 8 + 8 + 1 + 1 + 1 + 1 + 8 + 32 + 8 + 1 + 32 + 8 + 1 + 32 + 8 + 8 + 8 + 32 + 8 + 1 + 8 + 1 + 30;
+
+    /// Derives the PDA of a builder's `grant_nonce`-th grant.
+    pub fn address(origin_builder: &Pubkey, grant_nonce: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[GRANT_SEED, origin_builder.as_ref(), &grant_nonce.to_le_bytes()], &ID)
+    }
+
+    /// The registry bucket holding this grant's pubkey.
+    pub fn registry_bucket_index(&self) -> u64 {
+        self.registry_index / GRANTS_PER_REGISTRY_BUCKET as u64
+    }
+
+    /// Whether every tranche is paid and the last milestone can no longer be challenged, so the
+    /// grant can never be liquidated again.
+    pub fn is_settled(&self, challenge_window_seconds: i64, now: i64) -> Result<bool> {
+        let window_end = self
+            .last_payout_ts
+            .checked_add(challenge_window_seconds)
+            .ok_or(DGEError::ArithmeticOverflow)?;
+        Ok(self.tranches_completed >= self.total_tranches && now > window_end)
+    }
+
+    /// What a policy covering `covered_amount` pays after liquidation: its pro-rata share of the
+    /// undisbursed amount, never more than the cover itself. Rounded down.
+    pub fn coverage_payout_for(&self, covered_amount: u64) -> Result<u64> {
+        let exposure = self.total_grant_amount.saturating_sub(self.amount_disbursed);
+        if self.total_covered <= exposure {
+            return Ok(covered_amount);
+        }
+        let payout = mul_div(covered_amount as u128, exposure as u128, self.total_covered as u128, Rounding::Floor)
+            .ok_or(DGEError::ArithmeticOverflow)?;
+        u64::try_from(payout).map_err(|_| error!(DGEError::ArithmeticOverflow))
+    }
+
+    /// Builds the stable, CPI-facing view of this grant.
+    pub fn status(&self) -> GrantStatus {
+        GrantStatus {
+            builder: self.builder,
+            total_grant_amount: self.total_grant_amount,
+            builder_bond_amount: self.builder_bond_amount,
+            tranches_completed: self.tranches_completed,
+            total_tranches: self.total_tranches,
+            is_complete: self.tranches_completed >= self.total_tranches,
+            tier: self.tier,
+            is_frozen: self.is_frozen,
+            amount_disbursed: self.amount_disbursed,
+            is_funded: self.is_funded,
+        }
+    }
+}
+
+/// Stable layout returned by `verify_grant_standing`.
+/// Downstream programs deserialize this from return data (`Return<GrantStatus>`
+/// in Anchor) instead of depending on the internal `Grant` account layout.
+/// New fields must only ever be appended.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GrantStatus {
+    pub builder: Pubkey,            // 32
+    pub total_grant_amount: u64,    // 8
+    pub builder_bond_amount: u64,   // 8
+    pub tranches_completed: u8,     // 1
+    pub total_tranches: u8,         // 1
+    pub is_complete: bool,          // 1
+    pub tier: u8,                   // 1
+    pub is_frozen: bool,            // 1
+    pub amount_disbursed: u64,      // 8
+    pub is_funded: bool,            // 1
+}
+
+/// Per-milestone D-Metric pass scores of a grant. Entry `i` gates milestone `i + 1`.
+#[account]
+pub struct MilestoneSchedule {
+    pub grant: Pubkey,              // 32
+    pub required_scores: Vec<u8>,   // 4 + MAX_MILESTONES
+    pub bump: u8,                   // 1
+}
+
+impl MilestoneSchedule {
+    pub const LEN: usize = 32 + 4 + MAX_MILESTONES + 1;
+
+    /// The pass score for the 1-based `milestone_index`.
+    pub fn required_score(&self, milestone_index: u8) -> Result<u8> {
+        let position = (milestone_index as usize).checked_sub(1).ok_or(DGEError::MilestoneOutOfOrder)?;
+        self.required_scores
+            .get(position)
+            .copied()
+            .ok_or_else(|| error!(DGEError::MilestoneOutOfOrder))
+    }
+}
+
+/// Escrow holding a grant's full funding, in lamports, on top of the vault's own rent.
+#[account]
+pub struct GrantVault {
+    pub grant: Pubkey,              // 32
+    pub funded_amount: u64,         // 8
+    pub disbursed_amount: u64,      // 8
+    pub bump: u8,                   // 1
+}
+
+impl GrantVault {
+    pub const LEN: usize = 32 + 8 + 8 + 1;
+
+    /// Escrowed lamports not yet paid out.
+    pub fn remaining(&self) -> u64 {
+        self.funded_amount.saturating_sub(self.disbursed_amount)
+    }
+}
+
+/// An open dispute over a paid milestone. Holds the challenger's stake until resolution.
+#[account]
+pub struct Challenge {
+    pub grant: Pubkey,              // 32
+    pub challenger: Pubkey,         // 32
+    pub milestone_index: u8,        // 1
+    pub stake: u64,                 // 8
+    pub bump: u8,                   // 1
+}
+
+impl Challenge {
+    pub const LEN: usize = 32 + 32 + 1 + 8 + 1;
+}
+
+/// Proof of delivery for one paid tranche: what was paid, to whom, on what score, and when.
+/// Portable evidence of a builder's track record; check `is_revoked`, which is set when the
+/// milestone is later overturned by an upheld challenge.
+#[account]
+pub struct TrancheReceipt {
+    pub grant: Pubkey,              // 32
+    pub builder: Pubkey,            // 32 (payee at the time of payout)
+    pub milestone_index: u8,        // 1
+    pub tranche_amount: u64,        // 8 (gross, including the insurance fee)
+    pub builder_amount: u64,        // 8 (net, as received)
+    pub d_metric_score: u8,         // 1
+    pub paid_at: i64,               // 8
+    pub is_revoked: bool,           // 1
+    pub bump: u8,                   // 1
+}
+
+impl TrancheReceipt {
+    pub const LEN: usize = 32 + 32 + 1 + 8 + 8 + 1 + 8 + 1 + 1;
+
+    /// Derives the receipt PDA of a grant's milestone.
+    pub fn address(grant: &Pubkey, milestone_index: u8) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[TRANCHE_RECEIPT_SEED, grant.as_ref(), &[milestone_index]], &ID)
+    }
+}
+
+/// Per-builder reputation record, seeded by the builder's original pubkey.
+/// Grants reference their profile by address, so the record survives builder key rotation.
+#[account]
+pub struct BuilderProfile {
+    pub builder: Pubkey,            // 32
+    pub grant_nonce: u64,           // 8 (seed of the builder's next Grant PDA)
+    pub grants_started: u32,        // 4
+    pub grants_completed: u32,      // 4
+    pub liquidations: u32,          // 4
+    pub total_disbursed: u64,       // 8
+    pub score_sum: u64,             // 8
+    pub scores_recorded: u32,       // 4
+    pub d_metric: u8,               // 1 (average of every recorded milestone score)
+    pub bump: u8,                   // 1
+}
+
+impl BuilderProfile {
+    pub const LEN: usize = 32 + 8 + 4 + 4 + 4 + 8 + 8 + 4 + 1 + 1;
+
+    /// Folds a milestone score into the builder's running D-Metric average.
+    pub fn record_score(&mut self, score: u8) -> Result<()> {
+        self.score_sum = self.score_sum.checked_add(score as u64).ok_or(DGEError::ArithmeticOverflow)?;
+        self.scores_recorded = self.scores_recorded.checked_add(1).ok_or(DGEError::ArithmeticOverflow)?;
+        self.d_metric = (self.score_sum / self.scores_recorded as u64) as u8;
+        Ok(())
+    }
+
+    pub fn record_liquidation(&mut self) -> Result<()> {
+        self.liquidations = self.liquidations.checked_add(1).ok_or(DGEError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+/// Singleton index of every grant created by the program.
+///
+/// Enumeration pattern for clients:
+/// 1. Fetch the Registry PDA to read `grant_count`.
+/// 2. Derive bucket PDAs `0..bucket_count` with `RegistryBucket::address` (no RPC needed).
+/// 3. Fetch buckets in pages with `getMultipleAccounts`; bucket `i` holds grants
+///    `i * GRANTS_PER_REGISTRY_BUCKET ..` in creation order. Reclaimed grants leave
+///    `Pubkey::default()` in their slot.
+#[account]
+pub struct Registry {
+    pub grant_count: u64,           // 8
+    pub bucket_count: u64,          // 8
+    pub bump: u8,                   // 1
+}
+
+impl Registry {
+    pub const LEN: usize = 8 + 8 + 1;
+
+    /// The bucket the next grant will be appended to.
+    pub fn current_bucket(&self) -> u64 {
+        self.grant_count / GRANTS_PER_REGISTRY_BUCKET as u64
+    }
+}
+
+/// A fixed-capacity page of grant pubkeys in the Registry.
+#[account]
+pub struct RegistryBucket {
+    pub bucket_index: u64,          // 8
+    pub grants: Vec<Pubkey>,        // 4 + 32 * GRANTS_PER_REGISTRY_BUCKET
+    pub bump: u8,                   // 1
+}
+
+impl RegistryBucket {
+    pub const LEN: usize = 8 + 4 + 32 * GRANTS_PER_REGISTRY_BUCKET + 1;
+
+    /// Derives the PDA of the bucket at `bucket_index`.
+    pub fn address(bucket_index: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[REGISTRY_BUCKET_SEED, &bucket_index.to_le_bytes()], &ID)
+    }
+}
+
+/// Registered metric verifiers and the M-of-N threshold required to accept a metric.
+#[account]
+pub struct VerifierSet {
+    pub threshold: u8,              // 1
+    pub verifiers: Vec<Pubkey>,     // 4 + 32 * MAX_VERIFIERS
+    pub bump: u8,                   // 1
+}
+
+impl VerifierSet {
+    pub const LEN: usize = 1 + 4 + 32 * MAX_VERIFIERS + 1;
+
+    pub fn add(&mut self, verifier: Pubkey) -> Result<()> {
+        if self.verifiers.contains(&verifier) {
+            return err!(DGEError::VerifierAlreadyRegistered);
+        }
+        if self.verifiers.len() >= MAX_VERIFIERS {
+            return err!(DGEError::TooManyVerifiers);
+        }
+        self.verifiers.push(verifier);
+        Ok(())
+    }
+
+    pub fn set_threshold(&mut self, threshold: u8) -> Result<()> {
+        if threshold == 0 || threshold as usize > self.verifiers.len() {
+            return err!(DGEError::InvalidVerifierThreshold);
+        }
+        self.threshold = threshold;
+        Ok(())
+    }
+
+    /// Counts distinct registered verifiers that signed the transaction.
+    pub fn count_attestations(&self, accounts: &[AccountInfo]) -> usize {
+        let mut seen: Vec<Pubkey> = Vec::with_capacity(self.verifiers.len());
+        for account in accounts {
+            if account.is_signer && self.verifiers.contains(account.key) && !seen.contains(account.key) {
+                seen.push(*account.key);
+            }
+        }
+        seen.len()
+    }
+}
+
+/// A whitelisted Builder Bond collateral token, its governance-set haircut, and its oracle price.
+#[account]
+pub struct CollateralConfig {
+    pub mint: Pubkey,               // 32
+    pub vault: Pubkey,              // 32 (ATA of the bond vault authority PDA)
+    pub haircut_bps: u16,           // 2
+    pub is_enabled: bool,           // 1
+    pub bump: u8,                   // 1
+    pub oracle: Pubkey,             // 32 (governance-registered price poster)
+    pub price_in_usd_scaled: u128,  // 16 (USD per whole token, scaled by 10^18; 0 until posted)
+    pub price_updated_at: i64,      // 8
+}
+
+impl CollateralConfig {
+    pub const LEN: usize = 32 + 32 + 2 + 1 + 1 + 32 + 16 + 8;
+
+    /// The oracle's last price, provided one was posted at most `max_age_seconds` before `now`.
+    pub fn fresh_price(&self, now: i64, max_age_seconds: i64) -> Result<u128> {
+        if self.price_in_usd_scaled == 0 {
+            return err!(DGEError::PriceUnavailable);
+        }
+        if now.saturating_sub(self.price_updated_at) > max_age_seconds {
+            return err!(DGEError::StalePrice);
+        }
+        Ok(self.price_in_usd_scaled)
+    }
+}
+
+/// Program-wide pool funded by a fee on every tranche payout.
+#[account]
+pub struct InsuranceFund {
+    pub total_collected: u64,       // 8
+    pub total_withdrawn: u64,       // 8
+    pub bump: u8,                   // 1
+    pub outstanding_coverage: u64,  // 8 (sum of open coverage policies; governance cannot withdraw it)
+}
+
+impl InsuranceFund {
+    pub const LEN: usize = 8 + 8 + 1 + 8;
+}
+
+/// A community co-funder's recorded contribution to one grant, bounding the cover they can buy.
+#[account]
+pub struct CoFunding {
+    pub grant: Pubkey,              // 32
+    pub funder: Pubkey,             // 32
+    pub contributed_amount: u64,    // 8
+    pub bump: u8,                   // 1
+}
+
+impl CoFunding {
+    pub const LEN: usize = 32 + 32 + 8 + 1;
+}
+
+/// An insurance policy on one grant, paying up to `covered_amount` from the InsuranceFund if the
+/// grant is liquidated.
+#[account]
+pub struct Coverage {
+    pub grant: Pubkey,              // 32
+    pub holder: Pubkey,             // 32
+    pub covered_amount: u64,        // 8
+    pub premium_paid: u64,          // 8
+    pub bump: u8,                   // 1
+}
+
+impl Coverage {
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 1;
+}
+
+/// Governance-controlled tunables, previously hardcoded constants.
+#[account]
+pub struct ProgramConfig {
+    pub governance_authority: Pubkey,   // 32
+    pub pass_score: u8,                 // 1
+    pub excellent_metric_threshold: u64, // 8
+    pub excellent_score: u8,            // 1
+    pub good_metric_threshold: u64,     // 8
+    pub good_score: u8,                 // 1
+    pub failing_score: u8,              // 1
+    pub tier_bond_usd: [u64; 3],        // 24 (Tier 1, 2, 3)
+    pub tier_min_grant_amount: [u64; 2], // 16 (smallest Tier 2 and Tier 3 grant, in lamports)
+    pub insurance_fee_bps: u16,         // 2
+    pub challenge_window_seconds: i64,  // 8
+    pub min_challenge_stake: u64,       // 8
+    pub coverage_premium_bps: u16,      // 2
+    pub pass_score_step: u8,            // 1 (pass score increase per milestone)
+    pub bond_escalation_bps: u16,       // 2 (bond increase per prior liquidation)
+    pub max_bond_multiplier_bps: u16,   // 2
+    pub partial_payout_floor: Option<u8>, // 2 (score at or below which a tranche pays nothing; None: pass/fail)
+    pub challenger_bond_share_bps: u16, // 2 (bond share paid for an upheld challenge)
+    pub max_price_age_seconds: i64,     // 8 (oldest collateral price accepted for a bond)
+    pub bump: u8,                       // 1
+}
+
+impl ProgramConfig {
+    pub const LEN: usize = 32 + 1 + 8 + 1 + 8 + 1 + 1 + 24 + 16 + 2 + 8 + 8 + 2 + 1 + 2 + 2 + 2 + 2 + 8 + 1;
+
+    pub fn apply(&mut self, params: &ConfigParams) {
+        self.pass_score = params.pass_score;
+        self.excellent_metric_threshold = params.excellent_metric_threshold;
+        self.excellent_score = params.excellent_score;
+        self.good_metric_threshold = params.good_metric_threshold;
+        self.good_score = params.good_score;
+        self.failing_score = params.failing_score;
+        self.tier_bond_usd = params.tier_bond_usd;
+        self.tier_min_grant_amount = params.tier_min_grant_amount;
+        self.insurance_fee_bps = params.insurance_fee_bps;
+        self.challenge_window_seconds = params.challenge_window_seconds;
+        self.min_challenge_stake = params.min_challenge_stake;
+        self.coverage_premium_bps = params.coverage_premium_bps;
+        self.pass_score_step = params.pass_score_step;
+        self.bond_escalation_bps = params.bond_escalation_bps;
+        self.max_bond_multiplier_bps = params.max_bond_multiplier_bps;
+        self.partial_payout_floor = params.partial_payout_floor;
+        self.challenger_bond_share_bps = params.challenger_bond_share_bps;
+        self.max_price_age_seconds = params.max_price_age_seconds;
+    }
+
+    /// The per-milestone pass-score curve new grants are scheduled with.
+    pub fn pass_score_curve(&self) -> PassScoreCurve {
+        PassScoreCurve { base: self.pass_score, step: self.pass_score_step }
+    }
+
+    /// The score at or below which a milestone with pass score `required_score` pays nothing.
+    /// Equals `required_score` (plain pass/fail) when partial payouts are disabled.
+    pub fn partial_payout_floor_for(&self, required_score: u8) -> u8 {
+        match self.partial_payout_floor {
+            Some(floor) => floor.min(required_score),
+            None => required_score,
+        }
+    }
+
+    /// The configured pass/fail mapping, in the shape `dge_core::scoring` evaluates.
+    pub fn metric_bands(&self) -> MetricBands {
+        MetricBands {
+            excellent_threshold: self.excellent_metric_threshold,
+            excellent_score: self.excellent_score,
+            good_threshold: self.good_metric_threshold,
+            good_score: self.good_score,
+            failing_score: self.failing_score,
+        }
+    }
+
+    /// The tier of a grant of `total_grant_amount` lamports.
+    pub fn tier_for_grant_amount(&self, total_grant_amount: u64) -> GrantTier {
+        GrantTier::for_grant_amount(total_grant_amount, self.tier_min_grant_amount)
+    }
+
+    /// The Builder Bond requirement, in USD, for a grant tier.
+    pub fn bond_usd_for_tier(&self, tier: GrantTier) -> u64 {
+        self.tier_bond_usd[tier as usize - 1]
+    }
+
+    /// Computes the insurance fee owed on a given tranche, rounded down.
+    pub fn insurance_fee_for(&self, tranche_amount: u64) -> Result<u64> {
+        let fee = (tranche_amount as u128)
+            .checked_mul(self.insurance_fee_bps as u128)
+            .ok_or(DGEError::ArithmeticOverflow)?
+            / BPS_DENOMINATOR as u128;
+        u64::try_from(fee).map_err(|_| error!(DGEError::ArithmeticOverflow))
+    }
+
+    /// The part of a Builder Bond paid to the challenger of an upheld challenge, rounded down.
+    pub fn challenger_reward_for(&self, bond_amount: u64) -> Result<u64> {
+        let reward = mul_div(
+            bond_amount as u128,
+            self.challenger_bond_share_bps as u128,
+            BPS_DENOMINATOR as u128,
+            Rounding::Floor,
+        )
+        .ok_or(DGEError::ArithmeticOverflow)?;
+        u64::try_from(reward).map_err(|_| error!(DGEError::ArithmeticOverflow))
+    }
+
+    /// Computes the premium for `covered_amount` of insurance cover, rounded up in the fund's favor.
+    pub fn coverage_premium_for(&self, covered_amount: u64) -> Result<u64> {
+        let premium = mul_div(
+            covered_amount as u128,
+            self.coverage_premium_bps as u128,
+            BPS_DENOMINATOR as u128,
+            Rounding::Ceil,
+        )
+        .ok_or(DGEError::ArithmeticOverflow)?;
+        u64::try_from(premium).map_err(|_| error!(DGEError::ArithmeticOverflow))
+    }
+}
+
+/// Instruction payload for `initialize_config` and `update_config`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ConfigParams {
+    pub pass_score: u8,
+    pub excellent_metric_threshold: u64,
+    pub excellent_score: u8,
+    pub good_metric_threshold: u64,
+    pub good_score: u8,
+    pub failing_score: u8,
+    pub tier_bond_usd: [u64; 3],
+    pub tier_min_grant_amount: [u64; 2],
+    pub insurance_fee_bps: u16,
+    pub challenge_window_seconds: i64,
+    pub min_challenge_stake: u64,
+    pub coverage_premium_bps: u16,
+    pub pass_score_step: u8,
+    pub bond_escalation_bps: u16,
+    pub max_bond_multiplier_bps: u16,
+    /// `None` keeps payouts binary pass/fail; `Some(0)` pays any non-zero score proportionally.
+    pub partial_payout_floor: Option<u8>,
+    pub challenger_bond_share_bps: u16,
+    pub max_price_age_seconds: i64,
+}
+
+impl ConfigParams {
+    /// The reference values the program shipped with before they became configurable.
+    pub fn defaults() -> Self {
+        let bands = MetricBands::DEFAULT;
+        Self {
+            pass_score: D_METRIC_PASS_SCORE,
+            excellent_metric_threshold: bands.excellent_threshold,
+            excellent_score: bands.excellent_score,
+            good_metric_threshold: bands.good_threshold,
+            good_score: bands.good_score,
+            failing_score: bands.failing_score,
+            tier_bond_usd: [TIER_1_BUILDER_BOND_USD, TIER_2_BUILDER_BOND_USD, TIER_3_BUILDER_BOND_USD],
+            tier_min_grant_amount: [TIER_2_MIN_GRANT_TOKENS * LAMPORTS_PER_SOL, TIER_3_MIN_GRANT_TOKENS * LAMPORTS_PER_SOL],
+            insurance_fee_bps: 0,
+            challenge_window_seconds: DEFAULT_CHALLENGE_WINDOW_SECONDS,
+            min_challenge_stake: DEFAULT_MIN_CHALLENGE_STAKE,
+            coverage_premium_bps: DEFAULT_COVERAGE_PREMIUM_BPS,
+            pass_score_step: 0,
+            bond_escalation_bps: DEFAULT_BOND_ESCALATION_BPS,
+            max_bond_multiplier_bps: DEFAULT_MAX_BOND_MULTIPLIER_BPS,
+            partial_payout_floor: None,
+            challenger_bond_share_bps: DEFAULT_CHALLENGER_BOND_SHARE_BPS,
+            max_price_age_seconds: DEFAULT_MAX_PRICE_AGE_SECONDS,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let bands = MetricBands {
+            excellent_threshold: self.excellent_metric_threshold,
+            excellent_score: self.excellent_score,
+            good_threshold: self.good_metric_threshold,
+            good_score: self.good_score,
+            failing_score: self.failing_score,
+        };
+        let curve = PassScoreCurve { base: self.pass_score, step: self.pass_score_step };
+        if !bands.is_valid() || !curve.is_valid() {
+            return err!(DGEError::InvalidConfig);
+        }
+        if self.tier_bond_usd[0] == 0
+            || self.tier_bond_usd[0] > self.tier_bond_usd[1]
+            || self.tier_bond_usd[1] > self.tier_bond_usd[2]
+            || self.tier_min_grant_amount[0] == 0
+            || self.tier_min_grant_amount[0] > self.tier_min_grant_amount[1]
+        {
+            return err!(DGEError::InvalidConfig);
+        }
+        if self.insurance_fee_bps > MAX_INSURANCE_FEE_BPS {
+            return err!(DGEError::InsuranceFeeTooHigh);
+        }
+        if self.challenge_window_seconds <= 0 || self.max_price_age_seconds <= 0 {
+            return err!(DGEError::InvalidConfig);
+        }
+        // A zero premium would hand out free cover; above 100% cover is pointless.
+        if self.coverage_premium_bps == 0 || self.coverage_premium_bps as u64 > BPS_DENOMINATOR {
+            return err!(DGEError::InvalidConfig);
+        }
+        // The escalated bond can never drop below the base requirement.
+        if (self.max_bond_multiplier_bps as u64) < BPS_DENOMINATOR {
+            return err!(DGEError::InvalidConfig);
+        }
+        if self.partial_payout_floor.is_some_and(|floor| floor > MAX_D_METRIC_SCORE) {
+            return err!(DGEError::InvalidConfig);
+        }
+        if self.challenger_bond_share_bps as u64 > BPS_DENOMINATOR {
+            return err!(DGEError::InvalidConfig);
+        }
+        Ok(())
+    }
+}
+
+// --- EVENTS ---
+
+/// Emitted when a grant's builder authority moves to a new key.
+#[event]
+pub struct BuilderAuthorityRotated {
+    pub grant: Pubkey,
+    pub old_builder: Pubkey,
+    pub new_builder: Pubkey,
+    pub dao_authority: Pubkey,
+}
+
+/// Emitted when a builder reclaims the bond of a grant the DAO never funded.
+#[event]
+pub struct UnfundedGrantReclaimed {
+    pub grant: Pubkey,
+    pub builder: Pubkey,
+    pub bond_amount: u64,
+}
+
+/// Emitted when a completed grant's bond is returned to its builder.
+#[event]
+pub struct BondReleased {
+    pub grant: Pubkey,
+    pub builder: Pubkey,
+    pub bond_amount: u64,
+}
+
+/// Emitted when a liquidated grant's remaining bond is moved to the DAO treasury.
+#[event]
+pub struct LiquidatedBondSwept {
+    pub grant: Pubkey,
+    pub treasury: Pubkey,
+    pub bond_amount: u64,
+}
+
+/// Emitted when an umbrella grant funds one of its sub-grants.
+#[event]
+pub struct SubGrantFunded {
+    pub umbrella: Pubkey,
+    pub sub_grant: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when insurance cover is bought on a grant.
+#[event]
+pub struct CoveragePurchased {
+    pub grant: Pubkey,
+    pub holder: Pubkey,
+    pub covered_amount: u64,
+    pub premium: u64,
+}
+
+/// Emitted when a coverage policy pays out after liquidation.
+#[event]
+pub struct CoverageClaimed {
+    pub grant: Pubkey,
+    pub holder: Pubkey,
+    pub covered_amount: u64,
+    pub payout: u64,
+}
+
+/// Emitted when a coverage policy on a settled grant is closed without paying out.
+#[event]
+pub struct CoverageExpired {
+    pub grant: Pubkey,
+    pub holder: Pubkey,
+    pub covered_amount: u64,
+}
+
+/// Emitted when a paid milestone is challenged and the grant's payouts freeze.
+#[event]
+pub struct MilestoneChallenged {
+    pub grant: Pubkey,
+    pub challenger: Pubkey,
+    pub milestone_index: u8,
+    pub stake: u64,
+}
+
+/// Emitted when the DAO resolves a milestone challenge.
+#[event]
+pub struct ChallengeResolved {
+    pub grant: Pubkey,
+    pub challenger: Pubkey,
+    pub milestone_index: u8,
+    pub upheld: bool,
+    pub stake: u64,
+}
+
+/// Emitted when a milestone's D-Metric score liquidates the grant instead of paying a tranche.
+#[event]
+pub struct DMetricLiquidation {
+    pub grant: Pubkey,
+    pub milestone_index: u8,
+    pub d_metric_score: u8,
+    pub required_score: u8,
+}
+
+// --- ERROR HANDLING ---
+
+#[error_code]
+#[derive(PartialEq, Eq)]
+pub enum DGEError {
+    /// Deprecated: a failing score liquidates the grant and the payout succeeds (see the
+    /// `DMetricLiquidation` event), so this is no longer returned. Kept so error codes stay stable.
+    #[msg("The calculated D-Metric score is below the required threshold, triggering bond liquidation.")]
+    DMetricFailed,
+    #[msg("Cannot disburse a tranche; the grant has been liquidated and paused.")]
+    GrantLiquidated,
+    #[msg("Milestone submission is out of the required sequential order.")]
+    MilestoneOutOfOrder,
+    #[msg("The insurance fee exceeds the maximum allowed basis points.")]
+    InsuranceFeeTooHigh,
+    #[msg("The insurance fund does not hold enough lamports for this withdrawal.")]
+    InsufficientInsuranceFunds,
+    #[msg("An arithmetic operation overflowed.")]
+    ArithmeticOverflow,
+    #[msg("The new builder authority must be a valid key different from the current one.")]
+    InvalidBuilderAuthority,
+    #[msg("The grant tier is not defined in the tier table.")]
+    InvalidGrantTier,
+    #[msg("The Builder Bond could not be computed from the supplied price.")]
+    BondCalculationFailed,
+    #[msg("The posted Builder Bond is below the requirement for the declared tier.")]
+    InsufficientBuilderBond,
+    #[msg("The current registry bucket is full; open the next bucket first.")]
+    RegistryBucketFull,
+    #[msg("The current registry bucket still has room; no new bucket is needed.")]
+    RegistryBucketNotNeeded,
+    #[msg("Not enough registered verifiers signed the metric submission.")]
+    InsufficientVerifierSignatures,
+    #[msg("The verifier threshold must be between 1 and the number of registered verifiers.")]
+    InvalidVerifierThreshold,
+    #[msg("The verifier is already registered.")]
+    VerifierAlreadyRegistered,
+    #[msg("The verifier is not registered.")]
+    VerifierNotFound,
+    #[msg("The verifier set is at maximum capacity.")]
+    TooManyVerifiers,
+    #[msg("The signer is not the governance authority recorded in the program config.")]
+    Unauthorized,
+    #[msg("The config parameters are inconsistent (unordered bands, scores above 100, or unordered tiers).")]
+    InvalidConfig,
+    #[msg("Payouts are frozen while a milestone challenge is open.")]
+    GrantFrozen,
+    #[msg("Only the most recently paid milestone can be challenged.")]
+    MilestoneNotChallengeable,
+    #[msg("The challenge window for this milestone has closed.")]
+    ChallengeWindowClosed,
+    #[msg("The challenge stake is below the configured minimum.")]
+    ChallengeStakeTooLow,
+    #[msg("The bond collateral token is not currently accepted.")]
+    CollateralDisabled,
+    #[msg("The collateral haircut must be between 10_000 and MAX_COLLATERAL_HAIRCUT_BPS.")]
+    InvalidCollateralHaircut,
+    #[msg("Grant escrow can only be refunded once the grant is liquidated or every tranche is settled.")]
+    GrantNotLiquidated,
+    #[msg("A grant must have between 1 and MAX_MILESTONES milestone tranches.")]
+    InvalidMilestoneCount,
+    #[msg("The schedule needs one score (at most 100) per milestone and cannot change paid milestones.")]
+    InvalidMilestoneSchedule,
+    #[msg("Every milestone tranche of this grant has already been paid.")]
+    TrancheOverflow,
+    #[msg("The start time and funding deadline must not be in the past, nor the start more than a year out.")]
+    InvalidGrantTimeline,
+    #[msg("Milestones cannot be claimed before the grant's start time.")]
+    GrantNotStarted,
+    #[msg("The grant's funding deadline has passed.")]
+    FundingDeadlinePassed,
+    #[msg("The grant's funding deadline has not passed yet.")]
+    FundingDeadlineNotReached,
+    #[msg("The grant has been funded; its bond can no longer be reclaimed.")]
+    GrantAlreadyFunded,
+    #[msg("Coverage must be non-zero, within the co-funder's contribution, and within the grant's uncovered amount.")]
+    InvalidCoverageAmount,
+    #[msg("Payouts and liquidations must be invoked directly, not through another program.")]
+    UnexpectedCpi,
+    #[msg("Sub-grants must be funded by a top-level umbrella grant.")]
+    InvalidSubGrant,
+    #[msg("The sub-grant exceeds what is left of the umbrella grant.")]
+    SubGrantCapExceeded,
+    #[msg("Sub-grant milestones must be approved by the umbrella grant's builder.")]
+    SubGrantNotApproved,
+    #[msg("Cover can no longer be bought once a milestone has missed its pass score.")]
+    CoverageClosed,
+    #[msg("The collateral's oracle has not posted a price.")]
+    PriceUnavailable,
+    #[msg("The collateral's last oracle price is older than the configured maximum age.")]
+    StalePrice,
+    #[msg("The Builder Bond stays locked until every tranche is paid and the last challenge window closes, and is released only once.")]
+    BondLocked,
+    #[msg("A grant can only be funded once, even after its escrow has been refunded.")]
+    DuplicateFunding,
+    #[msg("Coverage can only expire once its grant is settled and can no longer be liquidated.")]
+    CoverageStillActive,
+    #[msg("A sub-grant's escrow goes back to its umbrella grant while the umbrella's vault is open.")]
+    UmbrellaVaultOpen,
+}
+
+// `From<DGEError>` and `DGEError::ALL` are generated from one list: the `From` match is
+// exhaustive, so a variant missing from the list fails to compile, and
+// `test_error_codes_decode` checks the list follows declaration order.
+macro_rules! dge_error_mapping {
+    ($($name:ident,)*) => {
+        /// Maps each Anchor error (reported on-chain as `6000 + index`) onto the stable cross-chain
+        /// code in `dge_core::error`, so clients decode Solana and non-Solana failures identically.
+        impl From<DGEError> for DgeErrorCode {
+            fn from(error: DGEError) -> Self {
+                match error {
+                    $(DGEError::$name => Self::$name,)*
+                }
+            }
+        }
+
+        impl DGEError {
+            /// Every variant in declaration (and therefore code) order.
+            pub const ALL: &'static [Self] = &[$(Self::$name,)*];
+
+            /// Decodes an Anchor custom error code from a failed transaction back into a `DGEError`.
+            pub fn from_anchor_code(code: u32) -> Option<Self> {
+                let index = code.checked_sub(anchor_lang::error::ERROR_CODE_OFFSET)?;
+                Self::ALL.get(index as usize).copied()
+            }
+        }
+    };
+}
+
+dge_error_mapping! {
+    DMetricFailed,
+    GrantLiquidated,
     MilestoneOutOfOrder,
+    InsuranceFeeTooHigh,
+    InsufficientInsuranceFunds,
+    ArithmeticOverflow,
+    InvalidBuilderAuthority,
+    InvalidGrantTier,
+    BondCalculationFailed,
+    InsufficientBuilderBond,
+    RegistryBucketFull,
+    RegistryBucketNotNeeded,
+    InsufficientVerifierSignatures,
+    InvalidVerifierThreshold,
+    VerifierAlreadyRegistered,
+    VerifierNotFound,
+    TooManyVerifiers,
+    Unauthorized,
+    InvalidConfig,
+    GrantFrozen,
+    MilestoneNotChallengeable,
+    ChallengeWindowClosed,
+    ChallengeStakeTooLow,
+    CollateralDisabled,
+    InvalidCollateralHaircut,
+    GrantNotLiquidated,
+    InvalidMilestoneCount,
+    InvalidMilestoneSchedule,
+    TrancheOverflow,
+    InvalidGrantTimeline,
+    GrantNotStarted,
+    FundingDeadlinePassed,
+    FundingDeadlineNotReached,
+    GrantAlreadyFunded,
+    InvalidCoverageAmount,
+    UnexpectedCpi,
+    InvalidSubGrant,
+    SubGrantCapExceeded,
+    SubGrantNotApproved,
+    CoverageClosed,
+    PriceUnavailable,
+    StalePrice,
+    BondLocked,
+    DuplicateFunding,
+    CoverageStillActive,
+    UmbrellaVaultOpen,
 }
@@ -3,71 +3,20 @@
 // It dynamically adjusts the required governance quorum percentage based on
 // the protocol's total Depth Score (a measure of TVL/staking).
 
-// We use the 'decimal-fixed-point' crate for precise, on-chain calculations
-// without using floating-point numbers, which are typically prohibited in smart contracts.
-// For this example, we will simulate the fixed-point math using standard integers,
-// where the result is scaled by 10^18 (the common fixed-point scale).
+// The fixed-point math, the quorum curve, and the bond sizing live in the shared
+// `dge-core` crate so that every chain computes bit-identical values.
+// This module re-exports them with Solana-flavoured error handling.
 
-// --- Fixed-Point Constants ---
-
-// Scale factor for fixed-point math. A value of 1_000_000_000_000_000_000 represents 1.0.
-const SCALE: u128 = 1_000_000_000_000_000_000;
-
-// --- Adaptive Quorum (AQ) Constants ---
-
-// The lowest possible Quorum percentage (Floor), represented as a scaled value.
-const QUORUM_FLOOR: u128 = 150_000_000_000_000_000; // 0.15 * SCALE (15%)
-
-// The highest possible Quorum percentage (Ceiling), represented as a scaled value.
-const QUORUM_CEILING: u128 = 450_000_000_000_000_000; // 0.45 * SCALE (45%)
-
-// The depth threshold (K) at which the quorum curve begins to flatten significantly.
-const DEPTH_THRESHOLD_K: u64 = 100_000_000; // $100 Million USD (Example threshold)
-
-// The difference between the ceiling and the floor
-const CEILING_MINUS_FLOOR: u128 = QUORUM_CEILING - QUORUM_FLOOR;
-
-// --- Builder Bond (BB) Constants ---
-
-// The required Builder Bond amount for a Tier 1 (Community) App, in USD equivalent.
-const TIER_1_BUILDER_BOND_USD: u64 = 300; 
+use dge_core::bond::BondError;
+use dge_core::error::DgeErrorCode;
+use solana_program::program_error::ProgramError;
 
 // --- Core Public Functions ---
 
 /// Calculates the required Adaptive Quorum percentage based on the protocol's Depth Score.
 /// The logic implements a simplified decay curve to scale the quorum between 15% and 45%.
-/// [The full calculation logic remains the same as previously defined]
-pub fn calculate_adaptive_quorum(total_protocol_depth: u64) -> u128 {
-    // 1. Calculate the normalized Depth Score (x = Depth / K).
-    let depth_u128: u128 = total_protocol_depth as u128;
-    let threshold_u128: u128 = DEPTH_THRESHOLD_K as u128;
-
-    let ratio_x: u128;
-    if total_protocol_depth == 0 {
-        ratio_x = 0;
-    } else {
-        ratio_x = (depth_u128.checked_mul(SCALE).unwrap_or(u128::MAX)) / threshold_u128;
-    }
-
-    // A simplified, inverse power decay function is used to approximate the curve: f(x) = SCALE / (SCALE + x)
-    let decay_term: u128;
-
-    let denominator_check = SCALE.checked_add(ratio_x);
-    if denominator_check.is_none() {
-        decay_term = 0;
-    } else {
-        let denominator = denominator_check.unwrap();
-        decay_term = (SCALE.checked_mul(SCALE).unwrap_or(u128::MAX)) / denominator;
-    }
-
-    // 2. Apply the decay term to the Quorum range:
-    let weighted_quorum = (decay_term.checked_mul(CEILING_MINUS_FLOOR).unwrap_or(u128::MAX)) / SCALE;
-
-    // 3. Add the Floor to get the final Adaptive Quorum:
-    let final_quorum = QUORUM_FLOOR.checked_add(weighted_quorum).unwrap_or(u128::MAX);
-
-    final_quorum.min(QUORUM_CEILING)
-}
+/// See `dge_core::quorum` for the curve itself.
+pub use dge_core::quorum::calculate_adaptive_quorum;
 
 /// Calculates the amount of $FST tokens required for the Builder Bond.
 /// 
@@ -82,28 +31,29 @@ pub fn calculate_adaptive_quorum(total_protocol_depth: u64) -> u128 {
 /// * The amount of $FST tokens (as a raw token amount) required for the bond.
 ///   (This value must be divided by the FST token's decimal value on the frontend for display)
 pub fn get_builder_bond_amount(fst_price_in_usd_scaled: u128) -> Result<u64, ProgramError> {
-    
-    // Bond Amount (FST) = (Bond Value USD * SCALE) / FST Price USD (Scaled)
-    
-    if fst_price_in_usd_scaled == 0 {
-        // Prevent division by zero if the token price is somehow zero
-        return Err(ProgramError::Custom(100)); // Price Oracle Error
-    }
+    dge_core::bond::get_builder_bond_amount(fst_price_in_usd_scaled).map_err(bond_error_to_program_error)
+}
 
-    let bond_usd_scaled = (TIER_1_BUILDER_BOND_USD as u128).checked_mul(SCALE).ok_or(ProgramError::Custom(101))?;
+/// Calculates the Builder Bond in FST base units (e.g., 10^-9 FST for a 9-decimal mint).
+///
+/// Unlike `get_builder_bond_amount`, nothing is truncated to whole tokens: the amount is
+/// rounded **up** to the next base unit so the bond always covers its full USD value.
+///
+/// # Arguments
+/// * `fst_price_in_usd_scaled` - The current price of 1 $FST token, scaled by 10^18.
+/// * `token_decimals` - The FST mint's decimal count.
+///
+/// # Returns
+/// * The raw base-unit amount to transfer for the bond.
+pub fn get_builder_bond_amount_in_base_units(fst_price_in_usd_scaled: u128, token_decimals: u8) -> Result<u64, ProgramError> {
+    dge_core::bond::get_builder_bond_amount_in_base_units(fst_price_in_usd_scaled, token_decimals)
+        .map_err(bond_error_to_program_error)
+}
 
-    // Perform the scaled division: (Bond_USD_Scaled * SCALE) / FST_Price_Scaled
-    // We multiply by SCALE again to maintain precision through the division, then divide by SCALE at the end.
-    let fst_amount_scaled = bond_usd_scaled.checked_mul(SCALE).ok_or(ProgramError::Custom(102))?
-        .checked_div(fst_price_in_usd_scaled)
-        .ok_or(ProgramError::Custom(103))?;
-    
-    // Un-scale the final FST token amount and convert to u64 for return
-    let fst_amount_raw = (fst_amount_scaled.checked_div(SCALE).ok_or(ProgramError::Custom(104))?)
-        .try_into()
-        .map_err(|_| ProgramError::Custom(105))?; // Conversion Error
-        
-    Ok(fst_amount_raw)
+/// Maps the chain-agnostic bond errors onto the shared, stable DGE error codes
+/// (see `dge_core::error`), e.g. a zero oracle price becomes `Custom(100)`.
+fn bond_error_to_program_error(error: BondError) -> ProgramError {
+    ProgramError::Custom(DgeErrorCode::from(error).code())
 }
 
 // --- Testing Section (For review and verification) ---
@@ -111,16 +61,10 @@ pub fn get_builder_bond_amount(fst_price_in_usd_scaled: u128) -> Result<u64, Pro
 #[cfg(test)]
 mod tests {
     use super::*;
-    use solana_program::program_error::ProgramError;
+    use dge_core::fixed_point::SCALE;
 
-    // Helper functions (omitted for brevity, assume they are the same as before)
-    fn quorum_to_percent_string(quorum: u128) -> String {
-        let percent = quorum as f64 / SCALE as f64 * 100.0;
-        format!("{:.2}%", percent)
-    }
-    
-    // --- AQ Tests (omitted for brevity, assume same as before) ---
-    // ... test_zero_depth, test_low_depth, etc.
+    // --- AQ Tests ---
+    // The quorum curve is tested alongside its implementation in dge-core (quorum.rs).
 
     // --- Builder Bond Tests ---
     
@@ -158,5 +102,14 @@ mod tests {
         println!("FST Price: $0.50 | Bond: {} FST", bond_amount);
         Ok(())
     }
-}
 
+    #[test]
+    // Test case: FST token price is $0.70 with 9 decimals. 428.571428571... FST rounds up to the next base unit.
+    fn test_bond_base_units_round_up() -> Result<(), ProgramError> {
+        let price = scale_price(0.7); // $0.70 USD
+        let bond_amount = get_builder_bond_amount_in_base_units(price, 9)?;
+        assert_eq!(bond_amount, 428_571_428_572);
+        println!("FST Price: $0.70 | Bond: {} base units", bond_amount);
+        Ok(())
+    }
+}
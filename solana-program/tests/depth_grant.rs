@@ -0,0 +1,1942 @@
+//! Integration tests for the DGE Anchor program.
+//! Each test boots a local bank with the compiled program (see tests/fixtures/README.md)
+//! and drives full grant flows through real transactions: setup, milestone payouts,
+//! D-Metric liquidation, and milestone challenges with clock manipulation.
+
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{
+    system_program, AccountDeserialize, AccountSerialize, AnchorDeserialize, Event, InstructionData, ToAccountMetas,
+};
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::token::{spl_token, TokenAccount};
+use base64::prelude::{Engine, BASE64_STANDARD};
+use dge_core::error::DgeErrorCode;
+use depth_grant::{
+    BuilderProfile, Challenge, CoFunding, CollateralConfig, ConfigParams, Coverage, DGEError, DMetricLiquidation, Grant,
+    GrantStatus, GrantVault, InsuranceFund, MilestoneSchedule, ProgramConfig, Registry, RegistryBucket, TrancheReceipt,
+    VerifierSet, BOND_VAULT_AUTHORITY_SEED, BUILDER_PROFILE_SEED, CHALLENGE_SEED, CO_FUNDING_SEED, COLLATERAL_SEED,
+    CONFIG_SEED, COVERAGE_SEED, DEFAULT_CHALLENGE_WINDOW_SECONDS, DEFAULT_CHALLENGER_BOND_SHARE_BPS,
+    DEFAULT_COVERAGE_PREMIUM_BPS, DEFAULT_MAX_PRICE_AGE_SECONDS, DEFAULT_MIN_CHALLENGE_STAKE,
+    GRANTS_PER_REGISTRY_BUCKET, GRANT_VAULT_SEED, INSURANCE_FUND_SEED, MAX_GRANT_START_DELAY_SECONDS, MAX_MILESTONES,
+    MILESTONE_SCHEDULE_SEED, REGISTRY_SEED, VERIFIER_SET_SEED,
+};
+use solana_program_test::{find_file, processor, read_file, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::bpf_loader_upgradeable;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::{AccountMeta, Instruction, InstructionError};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::sysvar;
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+/// 1 FST = $1.00, scaled by 10^18.
+const FST_PRICE_ONE_DOLLAR: u128 = 1_000_000_000_000_000_000;
+
+/// Decimal places of the test FST mint.
+const FST_DECIMALS: u8 = 9;
+
+/// Tier 1 bond at $1.00 per FST: 300 FST in 9-decimal base units.
+const TIER_1_BOND_AT_ONE_DOLLAR: u64 = 300_000_000_000;
+
+/// Tier 2 bond at $1.00 per FST: 1,500 FST in 9-decimal base units.
+const TIER_2_BOND_AT_ONE_DOLLAR: u64 = 1_500_000_000_000;
+
+/// FST the builder's token account starts with.
+const BUILDER_FST_BALANCE: u64 = 10_000_000_000_000;
+
+/// Grant size used by every test: 3 tranches of 1_000 SOL, plus one lamport of rounding
+/// remainder swept by the final tranche.
+const TOTAL_GRANT: u64 = 3_000 * LAMPORTS_PER_SOL + 1;
+const TRANCHES: u8 = 3;
+
+/// Metric values landing in the default "excellent" (95), "good" (80), and failing (60) bands.
+const PASSING_METRIC: u64 = 1_000;
+const GOOD_METRIC: u64 = 500;
+const FAILING_METRIC: u64 = 10;
+
+/// How long the DAO has to fund a grant opened by the helpers (1 week).
+const FUNDING_WINDOW_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+/// Test fee so the insurance accounting is exercised (1%).
+const INSURANCE_FEE_BPS: u16 = 100;
+
+/// Keys and PDAs shared by one test's bank.
+struct TestEnv {
+    context: ProgramTestContext,
+    upgrade_authority: Keypair,
+    dao: Keypair,
+    builder: Keypair,
+    challenger: Keypair,
+    oracle: Keypair,
+    verifiers: Vec<Keypair>,
+    fst_mint: Pubkey,
+    builder_fst_account: Pubkey,
+    challenger_fst_account: Pubkey,
+    treasury_fst_account: Pubkey,
+}
+
+impl TestEnv {
+    /// Boots a bank with the program deployed under the upgradeable loader, then runs the
+    /// one-time governance setup: config, registry, insurance fund, 2-of-3 verifier set, FST as
+    /// bond collateral at a 100% haircut with its own oracle, and the builder's profile.
+    async fn new() -> Self {
+        let upgrade_authority = Keypair::new();
+        let dao = Keypair::new();
+        let builder = Keypair::new();
+        let challenger = Keypair::new();
+        let oracle = Keypair::new();
+        let verifiers: Vec<Keypair> = (0..3).map(|_| Keypair::new()).collect();
+
+        let mut program_test = ProgramTest::default();
+        // The CPI wrapper only exists as a native processor, so it is added before SBF is preferred.
+        program_test.prefer_bpf(false);
+        program_test.add_program("cpi_wrapper", CPI_WRAPPER_ID, processor!(cpi_wrapper));
+        program_test.prefer_bpf(true);
+        add_upgradeable_program(&mut program_test, &upgrade_authority.pubkey());
+        for funded in [&upgrade_authority, &builder, &challenger] {
+            program_test.add_account(funded.pubkey(), system_account(100 * LAMPORTS_PER_SOL));
+        }
+        // The DAO escrows every grant it approves.
+        program_test.add_account(dao.pubkey(), system_account(10 * TOTAL_GRANT));
+        let fst_mint = Pubkey::new_unique();
+        let builder_fst_account = get_associated_token_address(&builder.pubkey(), &fst_mint);
+        program_test.add_account(fst_mint, mint_account(FST_DECIMALS));
+        program_test.add_account(builder_fst_account, token_account(&fst_mint, &builder.pubkey(), BUILDER_FST_BALANCE));
+        let challenger_fst_account = get_associated_token_address(&challenger.pubkey(), &fst_mint);
+        program_test.add_account(challenger_fst_account, token_account(&fst_mint, &challenger.pubkey(), 0));
+        let treasury_fst_account = get_associated_token_address(&dao.pubkey(), &fst_mint);
+        program_test.add_account(treasury_fst_account, token_account(&fst_mint, &dao.pubkey(), 0));
+
+        let mut env = Self {
+            context: program_test.start_with_context().await,
+            upgrade_authority,
+            dao,
+            builder,
+            challenger,
+            oracle,
+            verifiers,
+            fst_mint,
+            builder_fst_account,
+            challenger_fst_account,
+            treasury_fst_account,
+        };
+        env.bootstrap().await;
+        env
+    }
+
+    async fn bootstrap(&mut self) {
+        let mut params = ConfigParams::defaults();
+        params.insurance_fee_bps = INSURANCE_FEE_BPS;
+        let (program_data, _) = Pubkey::find_program_address(&[depth_grant::ID.as_ref()], &bpf_loader_upgradeable::id());
+
+        let initialize_config = instruction(
+            depth_grant::accounts::InitializeConfig {
+                config: config_address(),
+                upgrade_authority: self.upgrade_authority.pubkey(),
+                program: depth_grant::ID,
+                program_data,
+                system_program: system_program::ID,
+            },
+            depth_grant::instruction::InitializeConfig { governance_authority: self.dao.pubkey(), params },
+        );
+        let initialize_registry = instruction(
+            depth_grant::accounts::InitializeRegistry {
+                registry: registry_address(),
+                registry_bucket: RegistryBucket::address(0).0,
+                payer: self.dao.pubkey(),
+                system_program: system_program::ID,
+            },
+            depth_grant::instruction::InitializeRegistry {},
+        );
+        let initialize_insurance_fund = instruction(
+            depth_grant::accounts::InitializeInsuranceFund {
+                insurance_fund: insurance_fund_address(),
+                authority: self.dao.pubkey(),
+                config: config_address(),
+                system_program: system_program::ID,
+            },
+            depth_grant::instruction::InitializeInsuranceFund {},
+        );
+        let initialize_verifier_set = instruction(
+            depth_grant::accounts::InitializeVerifierSet {
+                verifier_set: verifier_set_address(),
+                authority: self.dao.pubkey(),
+                config: config_address(),
+                system_program: system_program::ID,
+            },
+            depth_grant::instruction::InitializeVerifierSet {
+                verifiers: self.verifiers.iter().map(|verifier| verifier.pubkey()).collect(),
+                threshold: 2,
+            },
+        );
+        let register_collateral = instruction(
+            depth_grant::accounts::RegisterCollateral {
+                collateral: collateral_address(&self.fst_mint),
+                mint: self.fst_mint,
+                vault: bond_vault_address(&self.fst_mint),
+                vault_authority: bond_vault_authority(),
+                authority: self.dao.pubkey(),
+                config: config_address(),
+                token_program: spl_token::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            },
+            depth_grant::instruction::RegisterCollateral { haircut_bps: 10_000, oracle: self.oracle.pubkey() },
+        );
+        let upgrade_authority = self.upgrade_authority.insecure_clone();
+        let dao = self.dao.insecure_clone();
+        self.send(
+            &[initialize_config, initialize_registry, initialize_insurance_fund, initialize_verifier_set],
+            &[&upgrade_authority, &dao],
+        )
+        .await
+        .unwrap();
+        self.send(&[register_collateral], &[&dao]).await.unwrap();
+
+        let builder = self.builder.insecure_clone();
+        let initialize_profile = instruction(
+            depth_grant::accounts::InitializeBuilderProfile {
+                builder_profile: builder_profile_address(&builder.pubkey()),
+                builder: builder.pubkey(),
+                system_program: system_program::ID,
+            },
+            depth_grant::instruction::InitializeBuilderProfile {},
+        );
+        self.send(&[initialize_profile], &[&builder]).await.unwrap();
+    }
+
+    /// Signs and submits `instructions` with the bank payer plus `signers`, on a fresh blockhash
+    /// so repeated identical instructions are not rejected as duplicates.
+    async fn send(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<(), BanksClientError> {
+        let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction =
+            Transaction::new_signed_with_payer(instructions, Some(&self.context.payer.pubkey()), &all_signers, blockhash);
+        self.context.banks_client.process_transaction(transaction).await
+    }
+
+    /// Sends `lamports` from the DAO to `recipient`.
+    async fn transfer_from_dao(&mut self, recipient: Pubkey, lamports: u64) {
+        let dao = self.dao.insecure_clone();
+        let transfer = system_instruction::transfer(&dao.pubkey(), &recipient, lamports);
+        self.send(&[transfer], &[&dao]).await.unwrap();
+    }
+
+    /// Simulates `verify_grant_standing` and decodes the `GrantStatus` from its return data.
+    async fn grant_standing(&mut self, grant: Pubkey) -> Result<GrantStatus, BanksClientError> {
+        let builder = self.fetch::<Grant>(grant).await.builder;
+        let verify = instruction(
+            depth_grant::accounts::VerifyGrantStanding { grant, builder },
+            depth_grant::instruction::VerifyGrantStanding {},
+        );
+        let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+        let payer = &self.context.payer;
+        let transaction = Transaction::new_signed_with_payer(&[verify], Some(&payer.pubkey()), &[payer], blockhash);
+        let simulation = self.context.banks_client.simulate_transaction(transaction).await?;
+        if let Some(Err(error)) = simulation.result {
+            return Err(error.into());
+        }
+        let return_data = simulation.simulation_details.and_then(|details| details.return_data).expect("no return data");
+        assert_eq!(return_data.program_id, depth_grant::ID);
+        Ok(GrantStatus::try_from_slice(&return_data.data).unwrap())
+    }
+
+    /// Simulates `instructions` and decodes every `T` event in the program's logs.
+    async fn simulate_events<T: Event>(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> Vec<T> {
+        let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction =
+            Transaction::new_signed_with_payer(instructions, Some(&self.context.payer.pubkey()), &all_signers, blockhash);
+        let simulation = self.context.banks_client.simulate_transaction(transaction).await.unwrap();
+        let logs = simulation.simulation_details.expect("no simulation details").logs;
+        logs.iter()
+            .filter_map(|log| log.strip_prefix("Program data: "))
+            .map(|data| BASE64_STANDARD.decode(data).unwrap())
+            .filter(|data| data.starts_with(&T::DISCRIMINATOR))
+            .map(|data| T::try_from_slice(&data[T::DISCRIMINATOR.len()..]).unwrap())
+            .collect()
+    }
+
+    async fn fetch<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let account = self.context.banks_client.get_account(address).await.unwrap().expect("account not found");
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    /// Overwrites the data of the program-owned account at `address` in place, e.g. to skip ahead
+    /// to a state that would take hundreds of transactions to reach.
+    async fn overwrite<T: AccountSerialize>(&mut self, address: Pubkey, state: &T) {
+        let mut account = self.context.banks_client.get_account(address).await.unwrap().expect("account not found");
+        let mut data = Vec::new();
+        state.try_serialize(&mut data).unwrap();
+        account.data[..data.len()].copy_from_slice(&data);
+        self.context.set_account(&address, &account.into());
+    }
+
+    async fn balance(&mut self, address: Pubkey) -> u64 {
+        self.context.banks_client.get_balance(address).await.unwrap()
+    }
+
+    /// The bank's current unix timestamp.
+    async fn now(&mut self) -> i64 {
+        let clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp
+    }
+
+    /// Moves the bank clock forward by `seconds`.
+    async fn advance_clock(&mut self, seconds: i64) {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    /// Opens and funds the builder's next grant (Tier 1, bond sized at $1.00 per FST) and
+    /// returns its address.
+    async fn initialize_grant(&mut self) -> Pubkey {
+        let grant = self.initialize_grant_with_bond(TIER_1_BOND_AT_ONE_DOLLAR).await.unwrap();
+        self.fund_grant(grant).await.unwrap();
+        grant
+    }
+
+    /// Opens a Tier 1 grant at $1.00 per FST, posting `bond` FST base units.
+    async fn initialize_grant_with_bond(&mut self, bond: u64) -> Result<Pubkey, BanksClientError> {
+        self.initialize_grant_with(bond, TRANCHES).await
+    }
+
+    /// Opens a grant that starts immediately and must be funded within `FUNDING_WINDOW_SECONDS`.
+    async fn initialize_grant_with(&mut self, bond: u64, milestone_tranches: u8) -> Result<Pubkey, BanksClientError> {
+        let now = self.now().await;
+        self.initialize_grant_scheduled(bond, milestone_tranches, now, now + FUNDING_WINDOW_SECONDS).await
+    }
+
+    /// Opens a grant in the same transaction as a fresh $1.00 FST price from the oracle.
+    async fn initialize_grant_scheduled(
+        &mut self,
+        bond: u64,
+        milestone_tranches: u8,
+        start_ts: i64,
+        funding_deadline_ts: i64,
+    ) -> Result<Pubkey, BanksClientError> {
+        let builder = self.builder.insecure_clone();
+        let oracle = self.oracle.insecure_clone();
+        let post_price = self.post_fst_price_instruction(FST_PRICE_ONE_DOLLAR);
+        let (grant, initialize_grant) =
+            self.initialize_grant_instruction(bond, milestone_tranches, start_ts, funding_deadline_ts).await;
+        self.send(&[post_price, initialize_grant], &[&builder, &oracle]).await?;
+        Ok(grant)
+    }
+
+    /// Builds `initialize_grant` for the builder's next grant, without refreshing the FST price.
+    async fn initialize_grant_instruction(
+        &mut self,
+        bond: u64,
+        milestone_tranches: u8,
+        start_ts: i64,
+        funding_deadline_ts: i64,
+    ) -> (Pubkey, Instruction) {
+        let builder = self.builder.insecure_clone();
+        let builder_profile = builder_profile_address(&builder.pubkey());
+        let nonce = self.fetch::<BuilderProfile>(builder_profile).await.grant_nonce;
+        let registry = self.fetch::<Registry>(registry_address()).await;
+        let grant = Grant::address(&builder.pubkey(), nonce).0;
+
+        let initialize_grant = instruction(
+            depth_grant::accounts::InitializeGrant {
+                builder: builder.pubkey(),
+                builder_profile,
+                grant,
+                milestone_schedule: milestone_schedule_address(&grant),
+                registry: registry_address(),
+                registry_bucket: RegistryBucket::address(registry.current_bucket()).0,
+                config: config_address(),
+                bond_mint: self.fst_mint,
+                collateral: collateral_address(&self.fst_mint),
+                builder_token_account: self.builder_fst_account,
+                bond_vault: bond_vault_address(&self.fst_mint),
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            },
+            depth_grant::instruction::InitializeGrant {
+                total_grant_amount: TOTAL_GRANT,
+                milestone_tranches,
+                builder_bond_amount: bond,
+                start_ts,
+                funding_deadline_ts,
+            },
+        );
+        (grant, initialize_grant)
+    }
+
+    fn post_fst_price_instruction(&self, price_in_usd_scaled: u128) -> Instruction {
+        instruction(
+            depth_grant::accounts::UpdateCollateralPrice {
+                collateral: collateral_address(&self.fst_mint),
+                oracle: self.oracle.pubkey(),
+            },
+            depth_grant::instruction::UpdateCollateralPrice { price_in_usd_scaled },
+        )
+    }
+
+    async fn update_config(&mut self, params: ConfigParams) -> Result<(), BanksClientError> {
+        let dao = self.dao.insecure_clone();
+        let update = instruction(
+            depth_grant::accounts::UpdateConfig { config: config_address(), governance_authority: dao.pubkey() },
+            depth_grant::instruction::UpdateConfig { params },
+        );
+        self.send(&[update], &[&dao]).await
+    }
+
+    async fn set_milestone_schedule(&mut self, grant: Pubkey, required_scores: Vec<u8>) -> Result<(), BanksClientError> {
+        let dao = self.dao.insecure_clone();
+        let set_schedule = instruction(
+            depth_grant::accounts::SetMilestoneSchedule {
+                grant,
+                milestone_schedule: milestone_schedule_address(&grant),
+                dao_authority: dao.pubkey(),
+                config: config_address(),
+            },
+            depth_grant::instruction::SetMilestoneSchedule { required_scores },
+        );
+        self.send(&[set_schedule], &[&dao]).await
+    }
+
+    async fn open_registry_bucket(&mut self) -> Result<(), BanksClientError> {
+        let registry = self.fetch::<Registry>(registry_address()).await;
+        let open = instruction(
+            depth_grant::accounts::OpenRegistryBucket {
+                registry: registry_address(),
+                registry_bucket: RegistryBucket::address(registry.bucket_count).0,
+                payer: self.context.payer.pubkey(),
+                system_program: system_program::ID,
+            },
+            depth_grant::instruction::OpenRegistryBucket {},
+        );
+        self.send(&[open], &[]).await
+    }
+
+    async fn fund_grant(&mut self, grant: Pubkey) -> Result<(), BanksClientError> {
+        let dao = self.dao.insecure_clone();
+        let fund = instruction(
+            depth_grant::accounts::FundGrant {
+                grant,
+                grant_vault: grant_vault_address(&grant),
+                dao_authority: dao.pubkey(),
+                config: config_address(),
+                system_program: system_program::ID,
+            },
+            depth_grant::instruction::FundGrant {},
+        );
+        self.send(&[fund], &[&dao]).await
+    }
+
+    async fn fund_sub_grant(&mut self, umbrella: Pubkey, sub_grant: Pubkey) -> Result<(), BanksClientError> {
+        let builder = self.builder.insecure_clone();
+        self.fund_sub_grant_consented_by(umbrella, sub_grant, &builder).await
+    }
+
+    async fn fund_sub_grant_consented_by(
+        &mut self,
+        umbrella: Pubkey,
+        sub_grant: Pubkey,
+        sub_grant_builder: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let builder = self.builder.insecure_clone();
+        let fund = instruction(
+            depth_grant::accounts::FundSubGrant {
+                umbrella,
+                umbrella_vault: grant_vault_address(&umbrella),
+                sub_grant,
+                sub_grant_vault: grant_vault_address(&sub_grant),
+                builder: builder.pubkey(),
+                sub_grant_builder: sub_grant_builder.pubkey(),
+                system_program: system_program::ID,
+            },
+            depth_grant::instruction::FundSubGrant {},
+        );
+        self.send(&[fund], &[&builder, sub_grant_builder]).await
+    }
+
+    async fn reclaim_unfunded_grant(&mut self, grant: Pubkey) -> Result<(), BanksClientError> {
+        let builder = self.builder.insecure_clone();
+        let registry_bucket = self.fetch::<Grant>(grant).await.registry_bucket_index();
+        let reclaim = instruction(
+            depth_grant::accounts::ReclaimUnfundedGrant {
+                builder: builder.pubkey(),
+                grant,
+                milestone_schedule: milestone_schedule_address(&grant),
+                registry_bucket: RegistryBucket::address(registry_bucket).0,
+                builder_profile: builder_profile_address(&builder.pubkey()),
+                bond_mint: self.fst_mint,
+                collateral: collateral_address(&self.fst_mint),
+                bond_vault: bond_vault_address(&self.fst_mint),
+                vault_authority: bond_vault_authority(),
+                builder_token_account: self.builder_fst_account,
+                token_program: spl_token::ID,
+            },
+            depth_grant::instruction::ReclaimUnfundedGrant {},
+        );
+        self.send(&[reclaim], &[&builder]).await
+    }
+
+    async fn rotate_builder_authority(
+        &mut self,
+        grant: Pubkey,
+        new_builder: Pubkey,
+        authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let builder = self.builder.insecure_clone();
+        let rotate = instruction(
+            depth_grant::accounts::RotateBuilderAuthority {
+                grant,
+                builder: builder.pubkey(),
+                dao_authority: authority.pubkey(),
+                config: config_address(),
+            },
+            depth_grant::instruction::RotateBuilderAuthority { new_builder },
+        );
+        self.send(&[rotate], &[&builder, authority]).await
+    }
+
+    /// Sends an `add_verifier`, `remove_verifier` or `set_verifier_threshold` instruction.
+    async fn configure_verifier_set(
+        &mut self,
+        data: impl InstructionData,
+        authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let configure = instruction(
+            depth_grant::accounts::ConfigureVerifierSet {
+                verifier_set: verifier_set_address(),
+                authority: authority.pubkey(),
+                config: config_address(),
+            },
+            data,
+        );
+        self.send(&[configure], &[authority]).await
+    }
+
+    async fn release_bond(&mut self, grant: Pubkey) -> Result<(), BanksClientError> {
+        let builder = self.builder.insecure_clone();
+        let release = instruction(
+            depth_grant::accounts::ReleaseBond {
+                builder: builder.pubkey(),
+                grant,
+                config: config_address(),
+                bond_mint: self.fst_mint,
+                collateral: collateral_address(&self.fst_mint),
+                bond_vault: bond_vault_address(&self.fst_mint),
+                vault_authority: bond_vault_authority(),
+                builder_token_account: self.builder_fst_account,
+                token_program: spl_token::ID,
+            },
+            depth_grant::instruction::ReleaseBond {},
+        );
+        self.send(&[release], &[&builder]).await
+    }
+
+    async fn sweep_liquidated_bond(&mut self, grant: Pubkey, authority: &Keypair) -> Result<(), BanksClientError> {
+        let sweep = instruction(
+            depth_grant::accounts::SweepLiquidatedBond {
+                grant,
+                dao_authority: authority.pubkey(),
+                config: config_address(),
+                bond_mint: self.fst_mint,
+                collateral: collateral_address(&self.fst_mint),
+                bond_vault: bond_vault_address(&self.fst_mint),
+                vault_authority: bond_vault_authority(),
+                dao_treasury_token_account: self.treasury_fst_account,
+                token_program: spl_token::ID,
+            },
+            depth_grant::instruction::SweepLiquidatedBond {},
+        );
+        self.send(&[sweep], &[authority]).await
+    }
+
+    async fn record_co_funding(&mut self, grant: Pubkey, funder: Pubkey, contributed_amount: u64) -> Result<(), BanksClientError> {
+        let dao = self.dao.insecure_clone();
+        let record = instruction(
+            depth_grant::accounts::RecordCoFunding {
+                grant,
+                co_funding: co_funding_address(&grant, &funder),
+                dao_authority: dao.pubkey(),
+                config: config_address(),
+                system_program: system_program::ID,
+            },
+            depth_grant::instruction::RecordCoFunding { funder, contributed_amount },
+        );
+        self.send(&[record], &[&dao]).await
+    }
+
+    async fn purchase_coverage(&mut self, grant: Pubkey, holder: &Keypair, covered_amount: u64) -> Result<(), BanksClientError> {
+        let purchase = instruction(
+            depth_grant::accounts::PurchaseCoverage {
+                holder: holder.pubkey(),
+                grant,
+                co_funding: co_funding_address(&grant, &holder.pubkey()),
+                coverage: coverage_address(&grant, &holder.pubkey()),
+                insurance_fund: insurance_fund_address(),
+                config: config_address(),
+                system_program: system_program::ID,
+            },
+            depth_grant::instruction::PurchaseCoverage { covered_amount },
+        );
+        self.send(&[purchase], &[holder]).await
+    }
+
+    async fn claim_coverage(&mut self, grant: Pubkey, holder: &Keypair) -> Result<(), BanksClientError> {
+        let claim = instruction(
+            depth_grant::accounts::ClaimCoverage {
+                holder: holder.pubkey(),
+                grant,
+                coverage: coverage_address(&grant, &holder.pubkey()),
+                insurance_fund: insurance_fund_address(),
+            },
+            depth_grant::instruction::ClaimCoverage {},
+        );
+        self.send(&[claim], &[holder]).await
+    }
+
+    async fn expire_coverage(&mut self, grant: Pubkey, holder: Pubkey) -> Result<(), BanksClientError> {
+        let expire = instruction(
+            depth_grant::accounts::ExpireCoverage {
+                holder,
+                grant,
+                coverage: coverage_address(&grant, &holder),
+                insurance_fund: insurance_fund_address(),
+                config: config_address(),
+            },
+            depth_grant::instruction::ExpireCoverage {},
+        );
+        self.send(&[expire], &[]).await
+    }
+
+    async fn withdraw_insurance_fund(
+        &mut self,
+        recipient: Pubkey,
+        amount: u64,
+        authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let withdraw = instruction(
+            depth_grant::accounts::WithdrawInsuranceFund {
+                insurance_fund: insurance_fund_address(),
+                authority: authority.pubkey(),
+                config: config_address(),
+                recipient,
+            },
+            depth_grant::instruction::WithdrawInsuranceFund { amount },
+        );
+        self.send(&[withdraw], &[authority]).await
+    }
+
+    async fn refund_grant_vault(&mut self, grant: Pubkey, dao_treasury: Pubkey) -> Result<(), BanksClientError> {
+        let dao = self.dao.insecure_clone();
+        let refund = instruction(
+            depth_grant::accounts::RefundGrantVault {
+                grant,
+                grant_vault: grant_vault_address(&grant),
+                dao_treasury,
+                dao_authority: dao.pubkey(),
+                config: config_address(),
+            },
+            depth_grant::instruction::RefundGrantVault {},
+        );
+        self.send(&[refund], &[&dao]).await
+    }
+
+    async fn refund_sub_grant_vault(&mut self, sub_grant: Pubkey, umbrella: Pubkey) -> Result<(), BanksClientError> {
+        let dao = self.dao.insecure_clone();
+        let refund = instruction(
+            depth_grant::accounts::RefundSubGrantVault {
+                sub_grant,
+                sub_grant_vault: grant_vault_address(&sub_grant),
+                umbrella,
+                umbrella_vault: grant_vault_address(&umbrella),
+                dao_authority: dao.pubkey(),
+                config: config_address(),
+            },
+            depth_grant::instruction::RefundSubGrantVault {},
+        );
+        self.send(&[refund], &[&dao]).await
+    }
+
+    async fn update_fst_collateral(&mut self, haircut_bps: u16, is_enabled: bool, oracle: Pubkey) {
+        let dao = self.dao.insecure_clone();
+        let update = instruction(
+            depth_grant::accounts::UpdateCollateral {
+                collateral: collateral_address(&self.fst_mint),
+                authority: dao.pubkey(),
+                config: config_address(),
+            },
+            depth_grant::instruction::UpdateCollateral { haircut_bps, is_enabled, oracle },
+        );
+        self.send(&[update], &[&dao]).await.unwrap();
+    }
+
+    /// Submits a milestone payout co-signed by the first `attestations` verifiers.
+    async fn payout(
+        &mut self,
+        grant: Pubkey,
+        milestone_index: u8,
+        metric: u64,
+        attestations: usize,
+    ) -> Result<(), BanksClientError> {
+        self.payout_with_accounts(grant, milestone_index, metric, attestations, vec![]).await
+    }
+
+    /// Submits a milestone with extra `remaining_accounts` after the verifier signatures.
+    async fn payout_with_accounts(
+        &mut self,
+        grant: Pubkey,
+        milestone_index: u8,
+        metric: u64,
+        attestations: usize,
+        extra_accounts: Vec<AccountMeta>,
+    ) -> Result<(), BanksClientError> {
+        let (payout, signers) = self.payout_instruction(grant, milestone_index, metric, attestations, extra_accounts).await;
+        let signers: Vec<&Keypair> = signers.iter().collect();
+        self.send(&[payout], &signers).await
+    }
+
+    /// Builds a milestone payout and the keypairs that must sign it: the builder, then the
+    /// first `attestations` verifiers.
+    async fn payout_instruction(
+        &mut self,
+        grant: Pubkey,
+        milestone_index: u8,
+        metric: u64,
+        attestations: usize,
+        extra_accounts: Vec<AccountMeta>,
+    ) -> (Instruction, Vec<Keypair>) {
+        let builder = self.builder.insecure_clone();
+        let verifiers: Vec<Keypair> = self.verifiers[..attestations].iter().map(Keypair::insecure_clone).collect();
+        // Recorded on the grant, so it still resolves after a builder rotation.
+        let builder_profile = self.fetch::<Grant>(grant).await.builder_profile;
+
+        let mut payout = instruction(
+            depth_grant::accounts::MilestonePayout {
+                grant,
+                builder: builder.pubkey(),
+                grant_vault: grant_vault_address(&grant),
+                milestone_schedule: milestone_schedule_address(&grant),
+                insurance_fund: insurance_fund_address(),
+                builder_profile,
+                verifier_set: verifier_set_address(),
+                config: config_address(),
+                receipt: TrancheReceipt::address(&grant, milestone_index).0,
+                instructions_sysvar: sysvar::instructions::ID,
+                system_program: system_program::ID,
+            },
+            depth_grant::instruction::CompleteMilestoneAndPayout { milestone_index, on_chain_metric_data: metric },
+        );
+        payout
+            .accounts
+            .extend(verifiers.iter().map(|verifier| AccountMeta::new_readonly(verifier.pubkey(), true)));
+        payout.accounts.extend(extra_accounts);
+
+        let mut signers = vec![builder];
+        signers.extend(verifiers);
+        (payout, signers)
+    }
+
+    async fn challenge(&mut self, grant: Pubkey, milestone_index: u8, stake: u64) -> Result<(), BanksClientError> {
+        let challenger = self.challenger.insecure_clone();
+        let challenge = instruction(
+            depth_grant::accounts::ChallengeMilestone {
+                grant,
+                challenge: challenge_address(&grant, milestone_index),
+                challenger: challenger.pubkey(),
+                config: config_address(),
+                system_program: system_program::ID,
+            },
+            depth_grant::instruction::ChallengeMilestone { milestone_index, stake },
+        );
+        self.send(&[challenge], &[&challenger]).await
+    }
+
+    async fn resolve(&mut self, grant: Pubkey, milestone_index: u8, upheld: bool) -> Result<(), BanksClientError> {
+        let dao = self.dao.insecure_clone();
+        let resolve = instruction(
+            depth_grant::accounts::ResolveChallenge {
+                grant,
+                challenge: challenge_address(&grant, milestone_index),
+                challenger: self.challenger.pubkey(),
+                builder: self.builder.pubkey(),
+                builder_profile: builder_profile_address(&self.builder.pubkey()),
+                dao_authority: dao.pubkey(),
+                config: config_address(),
+                receipt: TrancheReceipt::address(&grant, milestone_index).0,
+                bond_mint: self.fst_mint,
+                collateral: collateral_address(&self.fst_mint),
+                bond_vault: bond_vault_address(&self.fst_mint),
+                vault_authority: bond_vault_authority(),
+                challenger_token_account: self.challenger_fst_account,
+                token_program: spl_token::ID,
+            },
+            depth_grant::instruction::ResolveChallenge { upheld },
+        );
+        self.send(&[resolve], &[&dao]).await
+    }
+
+    async fn liquidate_bond(&mut self, grant: Pubkey) -> Result<(), BanksClientError> {
+        let dao = self.dao.insecure_clone();
+        let liquidate = instruction(
+            depth_grant::accounts::LiquidateBond {
+                grant,
+                dao_authority: dao.pubkey(),
+                builder_profile: builder_profile_address(&self.builder.pubkey()),
+                config: config_address(),
+                instructions_sysvar: sysvar::instructions::ID,
+            },
+            depth_grant::instruction::LiquidateBond {},
+        );
+        self.send(&[liquidate], &[&dao]).await
+    }
+}
+
+/// Address of `cpi_wrapper`, a stand-in for a third-party program wrapping DGE instructions.
+const CPI_WRAPPER_ID: Pubkey = Pubkey::new_from_array([0xc9; 32]);
+
+/// Forwards its instruction data and accounts (after the first, the target program) as a CPI.
+fn cpi_wrapper(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (target, forwarded) = accounts.split_first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let metas = forwarded
+        .iter()
+        .map(|account| AccountMeta { pubkey: *account.key, is_signer: account.is_signer, is_writable: account.is_writable })
+        .collect();
+    invoke(&Instruction { program_id: *target.key, accounts: metas, data: data.to_vec() }, accounts)
+}
+
+/// Wraps `inner` in a call to `cpi_wrapper`, which invokes it through a CPI.
+fn wrap_in_cpi(inner: Instruction) -> Instruction {
+    let mut accounts = vec![AccountMeta::new_readonly(inner.program_id, false)];
+    accounts.extend(inner.accounts);
+    Instruction { program_id: CPI_WRAPPER_ID, accounts, data: inner.data }
+}
+
+/// Registers the program under the upgradeable loader, mirroring a real `solana program deploy`,
+/// so `initialize_config` can check the upgrade authority recorded in its ProgramData account.
+fn add_upgradeable_program(program_test: &mut ProgramTest, upgrade_authority: &Pubkey) {
+    let elf = read_file(find_file("depth_grant.so").expect("depth_grant.so not found; see tests/fixtures/README.md"));
+    let (program_data, _) = Pubkey::find_program_address(&[depth_grant::ID.as_ref()], &bpf_loader_upgradeable::id());
+
+    // UpgradeableLoaderState::Program { programdata_address }, bincode-encoded.
+    let mut program_account = 2u32.to_le_bytes().to_vec();
+    program_account.extend_from_slice(program_data.as_ref());
+
+    // UpgradeableLoaderState::ProgramData { slot: 0, upgrade_authority_address: Some(..) }, then the ELF.
+    let mut program_data_account = 3u32.to_le_bytes().to_vec();
+    program_data_account.extend_from_slice(&0u64.to_le_bytes());
+    program_data_account.push(1);
+    program_data_account.extend_from_slice(upgrade_authority.as_ref());
+    program_data_account.extend_from_slice(&elf);
+
+    program_test.add_account(
+        depth_grant::ID,
+        Account {
+            lamports: LAMPORTS_PER_SOL,
+            data: program_account,
+            owner: bpf_loader_upgradeable::id(),
+            executable: true,
+            rent_epoch: 0,
+        },
+    );
+    program_test.add_account(
+        program_data,
+        Account {
+            lamports: 100 * LAMPORTS_PER_SOL,
+            data: program_data_account,
+            owner: bpf_loader_upgradeable::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+}
+
+fn system_account(lamports: u64) -> Account {
+    Account { lamports, data: vec![], owner: system_program::ID, executable: false, rent_epoch: 0 }
+}
+
+fn mint_account(decimals: u8) -> Account {
+    let mint = spl_token::state::Mint {
+        mint_authority: COption::None,
+        supply: BUILDER_FST_BALANCE,
+        decimals,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    };
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    mint.pack_into_slice(&mut data);
+    Account { lamports: LAMPORTS_PER_SOL, data, owner: spl_token::ID, executable: false, rent_epoch: 0 }
+}
+
+fn token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Account {
+    let account = spl_token::state::Account {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        delegate: COption::None,
+        state: spl_token::state::AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    };
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    account.pack_into_slice(&mut data);
+    Account { lamports: LAMPORTS_PER_SOL, data, owner: spl_token::ID, executable: false, rent_epoch: 0 }
+}
+
+fn instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction { program_id: depth_grant::ID, accounts: accounts.to_account_metas(None), data: data.data() }
+}
+
+fn config_address() -> Pubkey {
+    Pubkey::find_program_address(&[CONFIG_SEED], &depth_grant::ID).0
+}
+
+fn registry_address() -> Pubkey {
+    Pubkey::find_program_address(&[REGISTRY_SEED], &depth_grant::ID).0
+}
+
+fn insurance_fund_address() -> Pubkey {
+    Pubkey::find_program_address(&[INSURANCE_FUND_SEED], &depth_grant::ID).0
+}
+
+fn verifier_set_address() -> Pubkey {
+    Pubkey::find_program_address(&[VERIFIER_SET_SEED], &depth_grant::ID).0
+}
+
+fn builder_profile_address(builder: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[BUILDER_PROFILE_SEED, builder.as_ref()], &depth_grant::ID).0
+}
+
+fn milestone_schedule_address(grant: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[MILESTONE_SCHEDULE_SEED, grant.as_ref()], &depth_grant::ID).0
+}
+
+fn grant_vault_address(grant: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[GRANT_VAULT_SEED, grant.as_ref()], &depth_grant::ID).0
+}
+
+fn collateral_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[COLLATERAL_SEED, mint.as_ref()], &depth_grant::ID).0
+}
+
+fn bond_vault_authority() -> Pubkey {
+    Pubkey::find_program_address(&[BOND_VAULT_AUTHORITY_SEED], &depth_grant::ID).0
+}
+
+fn bond_vault_address(mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&bond_vault_authority(), mint)
+}
+
+fn coverage_address(grant: &Pubkey, holder: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[COVERAGE_SEED, grant.as_ref(), holder.as_ref()], &depth_grant::ID).0
+}
+
+fn co_funding_address(grant: &Pubkey, funder: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[CO_FUNDING_SEED, grant.as_ref(), funder.as_ref()], &depth_grant::ID).0
+}
+
+fn challenge_address(grant: &Pubkey, milestone_index: u8) -> Pubkey {
+    Pubkey::find_program_address(&[CHALLENGE_SEED, grant.as_ref(), &[milestone_index]], &depth_grant::ID).0
+}
+
+/// Asserts that `result` failed with the given program error.
+fn assert_dge_error(result: Result<(), BanksClientError>, expected: DGEError) {
+    assert_error_code(result, u32::from(expected));
+}
+
+/// Asserts that `result` failed with the given custom error code (DGE or Anchor framework).
+fn assert_error_code(result: Result<(), BanksClientError>, expected: u32) {
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(_, InstructionError::Custom(code)))) => {
+            assert_eq!(code, expected, "unexpected error code");
+        }
+        other => panic!("expected error code {}, got {:?}", expected, other),
+    }
+}
+
+#[tokio::test]
+// Test case: A grant is opened, all three milestones pass, and the grant completes.
+async fn test_grant_lifecycle_pays_every_tranche() {
+    let mut env = TestEnv::new().await;
+    let config: ProgramConfig = env.fetch(config_address()).await;
+    assert_eq!(config.governance_authority, env.dao.pubkey());
+
+    let grant_address = env.initialize_grant().await;
+    let grant: Grant = env.fetch(grant_address).await;
+    assert_eq!(grant.builder_bond_amount, TIER_1_BOND_AT_ONE_DOLLAR);
+    assert_eq!(grant.grant_nonce, 0);
+    let bucket: RegistryBucket = env.fetch(RegistryBucket::address(0).0).await;
+    assert_eq!(bucket.grants, vec![grant_address]);
+
+    let vault_address = grant_vault_address(&grant_address);
+    let vault_rent = env.balance(vault_address).await - TOTAL_GRANT;
+    let builder_before = env.balance(env.builder.pubkey()).await;
+    let fund_before = env.balance(insurance_fund_address()).await;
+
+    // TOTAL_GRANT does not divide evenly: every tranche but the last pays the nominal share,
+    // and the last pays exactly what is left.
+    let tranche = TOTAL_GRANT / TRANCHES as u64;
+    let final_tranche = TOTAL_GRANT - tranche * (TRANCHES as u64 - 1);
+    assert_ne!(final_tranche, tranche);
+    for milestone_index in 1..=TRANCHES {
+        env.payout(grant_address, milestone_index, PASSING_METRIC, 2).await.unwrap();
+        let grant: Grant = env.fetch(grant_address).await;
+        let expected = if milestone_index == TRANCHES { TOTAL_GRANT } else { tranche * milestone_index as u64 };
+        assert_eq!(grant.amount_disbursed, expected);
+    }
+
+    let grant: Grant = env.fetch(grant_address).await;
+    assert_eq!(grant.tranches_completed, TRANCHES);
+    assert!(!grant.is_liquidated);
+    assert_eq!(grant.status().amount_disbursed, TOTAL_GRANT);
+
+    let fees = config.insurance_fee_for(tranche).unwrap() * (TRANCHES as u64 - 1)
+        + config.insurance_fee_for(final_tranche).unwrap();
+    let fund: InsuranceFund = env.fetch(insurance_fund_address()).await;
+    assert_eq!(fund.total_collected, fees);
+
+    // The escrow is fully drained to the builder and the insurance fund, leaving only rent.
+    let vault: GrantVault = env.fetch(vault_address).await;
+    assert_eq!(vault.disbursed_amount, TOTAL_GRANT);
+    assert_eq!(env.balance(vault_address).await, vault_rent);
+    // Each paid tranche leaves a receipt whose rent the builder pays.
+    let mut receipts_rent = 0;
+    for milestone_index in 1..=TRANCHES {
+        let receipt_address = TrancheReceipt::address(&grant_address, milestone_index).0;
+        let receipt: TrancheReceipt = env.fetch(receipt_address).await;
+        let expected = if milestone_index == TRANCHES { final_tranche } else { tranche };
+        assert_eq!(receipt.tranche_amount, expected);
+        assert_eq!(receipt.builder_amount, expected - config.insurance_fee_for(expected).unwrap());
+        assert_eq!(receipt.builder, env.builder.pubkey());
+        assert_eq!(receipt.d_metric_score, 95);
+        assert!(!receipt.is_revoked);
+        receipts_rent += env.balance(receipt_address).await;
+    }
+    assert_eq!(env.balance(env.builder.pubkey()).await, builder_before + TOTAL_GRANT - fees - receipts_rent);
+    assert_eq!(env.balance(insurance_fund_address()).await, fund_before + fees);
+
+    let profile: BuilderProfile = env.fetch(builder_profile_address(&env.builder.pubkey())).await;
+    assert_eq!(profile.grants_started, 1);
+    assert_eq!(profile.grants_completed, 1);
+    assert_eq!(profile.total_disbursed, TOTAL_GRANT - fees);
+    assert_eq!(profile.d_metric, 95);
+}
+
+#[tokio::test]
+// Test case: The bond moves into the collateral vault and must cover the collateral haircut.
+async fn test_bond_escrowed_with_collateral_haircut() {
+    let mut env = TestEnv::new().await;
+    let collateral: CollateralConfig = env.fetch(collateral_address(&env.fst_mint)).await;
+    assert_eq!(collateral.vault, bond_vault_address(&env.fst_mint));
+
+    // At 130%, the $300 Tier 1 bond needs 390 FST.
+    let oracle = env.oracle.pubkey();
+    env.update_fst_collateral(13_000, true, oracle).await;
+    assert_dge_error(
+        env.initialize_grant_with_bond(TIER_1_BOND_AT_ONE_DOLLAR).await.map(|_| ()),
+        DGEError::InsufficientBuilderBond,
+    );
+    let haircut_bond = TIER_1_BOND_AT_ONE_DOLLAR * 13 / 10;
+    let grant_address = env.initialize_grant_with_bond(haircut_bond).await.unwrap();
+
+    let grant: Grant = env.fetch(grant_address).await;
+    assert_eq!(grant.bond_mint, env.fst_mint);
+    let vault: TokenAccount = env.fetch(bond_vault_address(&env.fst_mint)).await;
+    assert_eq!(vault.amount, haircut_bond);
+    let builder_account: TokenAccount = env.fetch(env.builder_fst_account).await;
+    assert_eq!(builder_account.amount, BUILDER_FST_BALANCE - haircut_bond);
+
+    env.update_fst_collateral(13_000, false, oracle).await;
+    assert_dge_error(env.initialize_grant_with_bond(haircut_bond).await.map(|_| ()), DGEError::CollateralDisabled);
+}
+
+#[tokio::test]
+// Test case: Bonds are priced from the collateral's registered oracle, and only from a recent price.
+async fn test_bond_priced_from_collateral_oracle() {
+    let mut env = TestEnv::new().await;
+    let oracle = env.oracle.insecure_clone();
+    let stranger = env.challenger.insecure_clone();
+
+    // Only the registered oracle posts prices, and never a zero price.
+    let mut forged_price = env.post_fst_price_instruction(FST_PRICE_ONE_DOLLAR);
+    forged_price.accounts[1].pubkey = stranger.pubkey();
+    assert_dge_error(env.send(&[forged_price], &[&stranger]).await, DGEError::Unauthorized);
+    let zero_price = env.post_fst_price_instruction(0);
+    assert_dge_error(env.send(&[zero_price], &[&oracle]).await, DGEError::PriceUnavailable);
+
+    // FST has no price until the oracle posts one.
+    let now = env.now().await;
+    let (_, initialize_grant) =
+        env.initialize_grant_instruction(TIER_1_BOND_AT_ONE_DOLLAR, TRANCHES, now, now + FUNDING_WINDOW_SECONDS).await;
+    let builder = env.builder.insecure_clone();
+    assert_dge_error(env.send(std::slice::from_ref(&initialize_grant), &[&builder]).await, DGEError::PriceUnavailable);
+
+    // At $0.50 per FST, the $300 bond needs 600 FST.
+    let half_dollar = env.post_fst_price_instruction(FST_PRICE_ONE_DOLLAR / 2);
+    env.send(&[half_dollar], &[&oracle]).await.unwrap();
+    let collateral: CollateralConfig = env.fetch(collateral_address(&env.fst_mint)).await;
+    assert_eq!(collateral.price_in_usd_scaled, FST_PRICE_ONE_DOLLAR / 2);
+    assert_dge_error(env.send(&[initialize_grant], &[&builder]).await, DGEError::InsufficientBuilderBond);
+
+    // A price older than the configured maximum age cannot price a bond.
+    env.advance_clock(DEFAULT_MAX_PRICE_AGE_SECONDS + 1).await;
+    let now = env.now().await;
+    let (_, initialize_grant) =
+        env.initialize_grant_instruction(2 * TIER_1_BOND_AT_ONE_DOLLAR, TRANCHES, now, now + FUNDING_WINDOW_SECONDS).await;
+    assert_dge_error(env.send(std::slice::from_ref(&initialize_grant), &[&builder]).await, DGEError::StalePrice);
+    let half_dollar = env.post_fst_price_instruction(FST_PRICE_ONE_DOLLAR / 2);
+    env.send(&[half_dollar, initialize_grant], &[&builder, &oracle]).await.unwrap();
+
+    // Replacing the oracle discards the old oracle's price.
+    env.update_fst_collateral(10_000, true, Pubkey::new_unique()).await;
+    let collateral: CollateralConfig = env.fetch(collateral_address(&env.fst_mint)).await;
+    assert_eq!(collateral.price_in_usd_scaled, 0);
+}
+
+#[tokio::test]
+// Test case: Milestones must be paid in order and carry the verifier threshold.
+async fn test_payout_requires_order_and_verifier_quorum() {
+    let mut env = TestEnv::new().await;
+    let grant_address = env.initialize_grant().await;
+
+    assert_dge_error(env.payout(grant_address, 2, PASSING_METRIC, 2).await, DGEError::MilestoneOutOfOrder);
+    assert_dge_error(env.payout(grant_address, 1, PASSING_METRIC, 1).await, DGEError::InsufficientVerifierSignatures);
+    env.payout(grant_address, 1, PASSING_METRIC, 3).await.unwrap();
+}
+
+#[tokio::test]
+// Test case: Tranche counts are bounded at initialization, and nothing is paid past the last one.
+async fn test_tranche_bounds() {
+    let mut env = TestEnv::new().await;
+    for milestone_tranches in [0, MAX_MILESTONES as u8 + 1] {
+        let result = env.initialize_grant_with(TIER_1_BOND_AT_ONE_DOLLAR, milestone_tranches).await;
+        assert_dge_error(result.map(|_| ()), DGEError::InvalidMilestoneCount);
+    }
+
+    let grant_address = env.initialize_grant_with(TIER_1_BOND_AT_ONE_DOLLAR, 1).await.unwrap();
+    env.fund_grant(grant_address).await.unwrap();
+    env.payout(grant_address, 1, PASSING_METRIC, 2).await.unwrap();
+    assert_dge_error(env.payout(grant_address, 2, PASSING_METRIC, 2).await, DGEError::TrancheOverflow);
+
+    let grant: Grant = env.fetch(grant_address).await;
+    assert_eq!(grant.tranches_completed, 1);
+    assert_eq!(grant.amount_disbursed, TOTAL_GRANT);
+}
+
+#[tokio::test]
+// Test case: Each milestone is gated by its own pass score from the grant's schedule.
+async fn test_per_milestone_pass_scores() {
+    let mut env = TestEnv::new().await;
+    let grant_address = env.initialize_grant().await;
+    let schedule: MilestoneSchedule = env.fetch(milestone_schedule_address(&grant_address)).await;
+    assert_eq!(schedule.required_scores, vec![75; TRANCHES as usize]);
+
+    assert_dge_error(
+        env.set_milestone_schedule(grant_address, vec![60, 80]).await,
+        DGEError::InvalidMilestoneSchedule,
+    );
+    env.set_milestone_schedule(grant_address, vec![60, 80, 95]).await.unwrap();
+
+    // A failing-band score (60) clears the eased first milestone, a "good" score the second.
+    env.payout(grant_address, 1, FAILING_METRIC, 2).await.unwrap();
+    env.payout(grant_address, 2, GOOD_METRIC, 2).await.unwrap();
+    assert_dge_error(
+        env.set_milestone_schedule(grant_address, vec![75, 80, 95]).await,
+        DGEError::InvalidMilestoneSchedule,
+    );
+
+    // The final milestone demands an "excellent" score, so 80 liquidates the grant.
+    env.payout(grant_address, 3, GOOD_METRIC, 2).await.unwrap();
+    let grant: Grant = env.fetch(grant_address).await;
+    assert_eq!(grant.tranches_completed, 2);
+    assert!(grant.is_liquidated);
+}
+
+#[tokio::test]
+// Test case: New grants are scheduled along the configured pass-score curve, capped at 100.
+async fn test_pass_score_curve_schedules_new_grants() {
+    let mut env = TestEnv::new().await;
+    let mut params = ConfigParams::defaults();
+    params.insurance_fee_bps = INSURANCE_FEE_BPS;
+    params.pass_score = 101;
+    assert_dge_error(env.update_config(params.clone()).await, DGEError::InvalidConfig);
+
+    params.pass_score = 80;
+    params.pass_score_step = 10;
+    env.update_config(params).await.unwrap();
+    let grant_address = env.initialize_grant_with(TIER_1_BOND_AT_ONE_DOLLAR, 4).await.unwrap();
+    let schedule: MilestoneSchedule = env.fetch(milestone_schedule_address(&grant_address)).await;
+    assert_eq!(schedule.required_scores, vec![80, 90, 100, 100]);
+}
+
+#[tokio::test]
+// Test case: With a partial-payout floor of 60 and a pass score of 90, a score of 80 releases
+// two thirds of the tranche; the shortfall stays in escrow until governance refunds it.
+async fn test_partial_payout_below_pass_score() {
+    let mut env = TestEnv::new().await;
+    let mut params = ConfigParams::defaults();
+    params.insurance_fee_bps = INSURANCE_FEE_BPS;
+    params.pass_score = 90;
+    params.partial_payout_floor = Some(60);
+    env.update_config(params).await.unwrap();
+    let grant_address = env.initialize_grant().await;
+
+    env.payout(grant_address, 1, GOOD_METRIC, 2).await.unwrap();
+    let full_tranche = TOTAL_GRANT / TRANCHES as u64;
+    let receipt: TrancheReceipt = env.fetch(TrancheReceipt::address(&grant_address, 1).0).await;
+    assert_eq!(receipt.d_metric_score, 80);
+    assert_eq!(receipt.tranche_amount, full_tranche * 2 / 3);
+    let grant: Grant = env.fetch(grant_address).await;
+    assert_eq!(grant.amount_disbursed, full_tranche);
+    assert!(!grant.is_liquidated);
+
+    // Escrow cannot be pulled mid-grant; once every tranche settles, only the shortfall is left.
+    let treasury = Pubkey::new_unique();
+    assert_dge_error(env.refund_grant_vault(grant_address, treasury).await, DGEError::GrantNotLiquidated);
+    env.payout(grant_address, 2, PASSING_METRIC, 2).await.unwrap();
+    env.payout(grant_address, 3, PASSING_METRIC, 2).await.unwrap();
+    let vault: GrantVault = env.fetch(grant_vault_address(&grant_address)).await;
+    assert_eq!(vault.remaining(), full_tranche - receipt.tranche_amount);
+    let vault_balance = env.balance(grant_vault_address(&grant_address)).await;
+    env.refund_grant_vault(grant_address, treasury).await.unwrap();
+    assert_eq!(env.balance(treasury).await, vault_balance);
+
+    // A score at the floor still liquidates.
+    let failing_grant = env.initialize_grant().await;
+    env.payout(failing_grant, 1, FAILING_METRIC, 2).await.unwrap();
+    assert!(env.fetch::<Grant>(failing_grant).await.is_liquidated);
+}
+
+#[tokio::test]
+// Test case: Payouts need a funded escrow, and each grant can only be funded once.
+async fn test_payout_requires_funded_escrow() {
+    let mut env = TestEnv::new().await;
+    let grant_address = env.initialize_grant_with_bond(TIER_1_BOND_AT_ONE_DOLLAR).await.unwrap();
+
+    assert_error_code(
+        env.payout(grant_address, 1, PASSING_METRIC, 2).await,
+        anchor_lang::error::ErrorCode::AccountNotInitialized.into(),
+    );
+    env.fund_grant(grant_address).await.unwrap();
+    assert!(env.fund_grant(grant_address).await.is_err());
+    env.payout(grant_address, 1, PASSING_METRIC, 2).await.unwrap();
+
+    // Escrow backing a grant in good standing cannot be pulled.
+    let treasury = env.dao.pubkey();
+    assert_dge_error(env.refund_grant_vault(grant_address, treasury).await, DGEError::GrantNotLiquidated);
+
+    // Refunding a settled grant closes its vault, but the grant still cannot be funded again.
+    env.payout(grant_address, 2, PASSING_METRIC, 2).await.unwrap();
+    env.payout(grant_address, 3, PASSING_METRIC, 2).await.unwrap();
+    env.refund_grant_vault(grant_address, treasury).await.unwrap();
+    assert_dge_error(env.fund_grant(grant_address).await, DGEError::DuplicateFunding);
+}
+
+#[tokio::test]
+// Test case: Milestones cannot be claimed before the grant's start time.
+async fn test_payout_waits_for_start() {
+    let mut env = TestEnv::new().await;
+    let now = env.now().await;
+    let start_ts = now + 3_600;
+    let grant_address = env
+        .initialize_grant_scheduled(TIER_1_BOND_AT_ONE_DOLLAR, TRANCHES, start_ts, now + FUNDING_WINDOW_SECONDS)
+        .await
+        .unwrap();
+    env.fund_grant(grant_address).await.unwrap();
+
+    assert_dge_error(env.payout(grant_address, 1, PASSING_METRIC, 2).await, DGEError::GrantNotStarted);
+    env.advance_clock(3_600).await;
+    env.payout(grant_address, 1, PASSING_METRIC, 2).await.unwrap();
+
+    let grant: Grant = env.fetch(grant_address).await;
+    assert_eq!(grant.start_ts, start_ts);
+    assert_eq!(grant.tranches_completed, 1);
+}
+
+#[tokio::test]
+// Test case: If the DAO misses the funding deadline, the builder alone reclaims the bond and
+// closes the grant; governance can no longer fund it. Start times must be near-term.
+async fn test_unfunded_grant_reclaim() {
+    let mut env = TestEnv::new().await;
+    let now = env.now().await;
+    let deadline = now + FUNDING_WINDOW_SECONDS;
+    let too_late = now + MAX_GRANT_START_DELAY_SECONDS + 1;
+    for (start_ts, funding_deadline_ts) in [(now, now - 1), (now - 1, deadline), (too_late, deadline)] {
+        let rejected = env.initialize_grant_scheduled(TIER_1_BOND_AT_ONE_DOLLAR, TRANCHES, start_ts, funding_deadline_ts).await;
+        assert_dge_error(rejected.map(|_| ()), DGEError::InvalidGrantTimeline);
+    }
+
+    let grant_address = env.initialize_grant_with_bond(TIER_1_BOND_AT_ONE_DOLLAR).await.unwrap();
+    assert_dge_error(env.reclaim_unfunded_grant(grant_address).await, DGEError::FundingDeadlineNotReached);
+
+    env.advance_clock(FUNDING_WINDOW_SECONDS + 1).await;
+    assert_dge_error(env.fund_grant(grant_address).await, DGEError::FundingDeadlinePassed);
+
+    let builder_lamports = env.balance(env.builder.pubkey()).await;
+    let grant_rent = env.balance(grant_address).await + env.balance(milestone_schedule_address(&grant_address)).await;
+    env.reclaim_unfunded_grant(grant_address).await.unwrap();
+
+    let builder_account: TokenAccount = env.fetch(env.builder_fst_account).await;
+    assert_eq!(builder_account.amount, BUILDER_FST_BALANCE);
+    let vault: TokenAccount = env.fetch(bond_vault_address(&env.fst_mint)).await;
+    assert_eq!(vault.amount, 0);
+    // The grant and its schedule are closed, refunding their rent to the builder.
+    assert!(env.context.banks_client.get_account(grant_address).await.unwrap().is_none());
+    assert_eq!(env.balance(env.builder.pubkey()).await, builder_lamports + grant_rent);
+    // The grant leaves the registry and the builder's record.
+    let bucket: RegistryBucket = env.fetch(RegistryBucket::address(0).0).await;
+    assert_eq!(bucket.grants, vec![Pubkey::default()]);
+    let profile: BuilderProfile = env.fetch(builder_profile_address(&env.builder.pubkey())).await;
+    assert_eq!(profile.grants_started, 0);
+}
+
+#[tokio::test]
+// Test case: A funded grant's bond stays locked after the funding deadline, including once
+// the grant is complete and its vault has been refunded and closed.
+async fn test_funded_grant_cannot_be_reclaimed() {
+    let mut env = TestEnv::new().await;
+    let grant_address = env.initialize_grant().await;
+    env.advance_clock(FUNDING_WINDOW_SECONDS + 1).await;
+    assert_dge_error(env.reclaim_unfunded_grant(grant_address).await, DGEError::GrantAlreadyFunded);
+
+    for milestone_index in 1..=TRANCHES {
+        env.payout(grant_address, milestone_index, PASSING_METRIC, 2).await.unwrap();
+    }
+    env.refund_grant_vault(grant_address, Pubkey::new_unique()).await.unwrap();
+    assert!(env.context.banks_client.get_account(grant_vault_address(&grant_address)).await.unwrap().is_none());
+    assert_dge_error(env.reclaim_unfunded_grant(grant_address).await, DGEError::GrantAlreadyFunded);
+}
+
+#[tokio::test]
+// Test case: A failing D-Metric liquidates the grant, the state persists, payouts stop,
+// and governance reclaims the escrow.
+async fn test_failed_d_metric_liquidates_grant() {
+    let mut env = TestEnv::new().await;
+    let grant_address = env.initialize_grant().await;
+
+    env.payout(grant_address, 1, FAILING_METRIC, 2).await.unwrap();
+
+    let grant: Grant = env.fetch(grant_address).await;
+    assert!(grant.is_liquidated);
+    assert_eq!(grant.tranches_completed, 0);
+    // No tranche was paid, so no receipt survives.
+    let receipt = TrancheReceipt::address(&grant_address, 1).0;
+    assert!(env.context.banks_client.get_account(receipt).await.unwrap().is_none());
+    let profile: BuilderProfile = env.fetch(builder_profile_address(&env.builder.pubkey())).await;
+    assert_eq!(profile.liquidations, 1);
+
+    assert_dge_error(env.payout(grant_address, 1, PASSING_METRIC, 2).await, DGEError::GrantLiquidated);
+
+    let treasury = Pubkey::new_unique();
+    let vault_balance = env.balance(grant_vault_address(&grant_address)).await;
+    env.refund_grant_vault(grant_address, treasury).await.unwrap();
+    assert_eq!(env.balance(treasury).await, vault_balance);
+}
+
+#[tokio::test]
+// Test case: The grant tier is derived from the grant amount, so a large grant cannot post a
+// Tier 1 bond.
+async fn test_tier_follows_grant_amount() {
+    let mut env = TestEnv::new().await;
+    let grant_address = env.initialize_grant().await;
+    assert_eq!(env.fetch::<Grant>(grant_address).await.tier, 1);
+
+    let mut params = ConfigParams::defaults();
+    params.insurance_fee_bps = INSURANCE_FEE_BPS;
+    params.tier_min_grant_amount = [0, TOTAL_GRANT];
+    assert_dge_error(env.update_config(params.clone()).await, DGEError::InvalidConfig);
+    params.tier_min_grant_amount = [TOTAL_GRANT, 10 * TOTAL_GRANT];
+    env.update_config(params).await.unwrap();
+
+    assert_dge_error(
+        env.initialize_grant_with_bond(TIER_1_BOND_AT_ONE_DOLLAR).await.map(|_| ()),
+        DGEError::InsufficientBuilderBond,
+    );
+    let growth_grant = env.initialize_grant_with_bond(TIER_2_BOND_AT_ONE_DOLLAR).await.unwrap();
+    assert_eq!(env.fetch::<Grant>(growth_grant).await.tier, 2);
+}
+
+#[tokio::test]
+// Test case: A builder with a liquidation on record posts a 50% larger bond for the next grant.
+async fn test_repeat_offender_bond_escalation() {
+    let mut env = TestEnv::new().await;
+    let grant_address = env.initialize_grant().await;
+    env.payout(grant_address, 1, FAILING_METRIC, 2).await.unwrap();
+
+    assert_dge_error(
+        env.initialize_grant_with_bond(TIER_1_BOND_AT_ONE_DOLLAR).await.map(|_| ()),
+        DGEError::InsufficientBuilderBond,
+    );
+    let escalated_bond = TIER_1_BOND_AT_ONE_DOLLAR * 3 / 2;
+    let next_grant = env.initialize_grant_with_bond(escalated_bond).await.unwrap();
+    let grant: Grant = env.fetch(next_grant).await;
+    assert_eq!(grant.builder_bond_amount, escalated_bond);
+}
+
+#[tokio::test]
+// Test case: An umbrella grant funds a sub-grant from its escrow up to what it has left, and
+// the sub-grant's milestones need the umbrella builder's approval.
+async fn test_sub_grant_funded_from_umbrella() {
+    let mut env = TestEnv::new().await;
+    let umbrella = env.initialize_grant().await;
+    let sub_grant = env.initialize_grant_with_bond(TIER_1_BOND_AT_ONE_DOLLAR).await.unwrap();
+
+    // The sub-grant's builder has to consent to the umbrella.
+    let stranger = env.challenger.insecure_clone();
+    assert_dge_error(env.fund_sub_grant_consented_by(umbrella, sub_grant, &stranger).await, DGEError::Unauthorized);
+    env.fund_sub_grant(umbrella, sub_grant).await.unwrap();
+    let umbrella_grant: Grant = env.fetch(umbrella).await;
+    assert_eq!(umbrella_grant.sub_grant_allocated, TOTAL_GRANT);
+    assert_eq!(umbrella_grant.amount_disbursed, TOTAL_GRANT);
+    assert_eq!(env.fetch::<Grant>(sub_grant).await.parent_grant, umbrella);
+    let sub_grant_vault: GrantVault = env.fetch(grant_vault_address(&sub_grant)).await;
+    assert_eq!(sub_grant_vault.funded_amount, TOTAL_GRANT);
+
+    // The umbrella is fully allocated, and sub-grants cannot nest further.
+    let second = env.initialize_grant_with_bond(TIER_1_BOND_AT_ONE_DOLLAR).await.unwrap();
+    assert_dge_error(env.fund_sub_grant(umbrella, second).await, DGEError::SubGrantCapExceeded);
+    assert_dge_error(env.fund_sub_grant(sub_grant, second).await, DGEError::InvalidSubGrant);
+
+    assert_dge_error(env.payout(sub_grant, 1, PASSING_METRIC, 2).await, DGEError::SubGrantNotApproved);
+    let approval = vec![AccountMeta::new_readonly(umbrella, false), AccountMeta::new_readonly(env.builder.pubkey(), true)];
+    env.payout_with_accounts(sub_grant, 1, PASSING_METRIC, 2, approval.clone()).await.unwrap();
+    assert_eq!(env.fetch::<Grant>(sub_grant).await.tranches_completed, 1);
+
+    // A liquidated sub-grant's escrow goes back to the umbrella, not to the treasury.
+    env.payout_with_accounts(sub_grant, 2, FAILING_METRIC, 2, approval).await.unwrap();
+    assert!(env.fetch::<Grant>(sub_grant).await.is_liquidated);
+    let treasury = Pubkey::new_unique();
+    assert_dge_error(env.refund_grant_vault(sub_grant, treasury).await, DGEError::UmbrellaVaultOpen);
+    assert_dge_error(env.refund_sub_grant_vault(sub_grant, sub_grant).await, DGEError::InvalidSubGrant);
+
+    let returned = env.fetch::<GrantVault>(grant_vault_address(&sub_grant)).await.remaining();
+    let sub_grant_vault_balance = env.balance(grant_vault_address(&sub_grant)).await;
+    let umbrella_vault_balance = env.balance(grant_vault_address(&umbrella)).await;
+    env.refund_sub_grant_vault(sub_grant, umbrella).await.unwrap();
+    assert_eq!(env.balance(grant_vault_address(&umbrella)).await, umbrella_vault_balance + sub_grant_vault_balance);
+    let umbrella_grant: Grant = env.fetch(umbrella).await;
+    assert_eq!(umbrella_grant.amount_disbursed, TOTAL_GRANT - returned);
+    assert_eq!(umbrella_grant.sub_grant_allocated, TOTAL_GRANT - returned);
+    let umbrella_vault: GrantVault = env.fetch(grant_vault_address(&umbrella)).await;
+    assert_eq!(umbrella_vault.disbursed_amount, TOTAL_GRANT - returned);
+    assert_eq!(env.balance(treasury).await, 0);
+}
+
+#[tokio::test]
+// Test case: A co-funder buys cover on a grant, priced at the configured premium, and is paid
+// from the InsuranceFund only once the grant is liquidated.
+async fn test_coverage_pays_out_after_liquidation() {
+    let mut env = TestEnv::new().await;
+    let co_funder = env.challenger.insecure_clone();
+    let grant_address = env.initialize_grant().await;
+    env.payout(grant_address, 1, PASSING_METRIC, 2).await.unwrap();
+
+    // Only recorded co-funders can buy cover.
+    assert_error_code(
+        env.purchase_coverage(grant_address, &co_funder, LAMPORTS_PER_SOL).await,
+        anchor_lang::error::ErrorCode::AccountNotInitialized.into(),
+    );
+    env.record_co_funding(grant_address, co_funder.pubkey(), TOTAL_GRANT).await.unwrap();
+    let co_funding: CoFunding = env.fetch(co_funding_address(&grant_address, &co_funder.pubkey())).await;
+    assert_eq!(co_funding.contributed_amount, TOTAL_GRANT);
+
+    // Cover is capped at what the grant has yet to disburse.
+    let exposure = TOTAL_GRANT - TOTAL_GRANT / TRANCHES as u64;
+    assert_dge_error(env.purchase_coverage(grant_address, &co_funder, exposure + 1).await, DGEError::InvalidCoverageAmount);
+    assert_dge_error(env.purchase_coverage(grant_address, &co_funder, 0).await, DGEError::InvalidCoverageAmount);
+
+    let covered_amount = 10 * LAMPORTS_PER_SOL;
+    let config: ProgramConfig = env.fetch(config_address()).await;
+    let premium = config.coverage_premium_for(covered_amount).unwrap();
+    assert_eq!(premium, covered_amount * DEFAULT_COVERAGE_PREMIUM_BPS as u64 / 10_000);
+    let fund_before: InsuranceFund = env.fetch(insurance_fund_address()).await;
+    env.purchase_coverage(grant_address, &co_funder, covered_amount).await.unwrap();
+
+    let coverage: Coverage = env.fetch(coverage_address(&grant_address, &co_funder.pubkey())).await;
+    assert_eq!(coverage.covered_amount, covered_amount);
+    assert_eq!(coverage.premium_paid, premium);
+    let fund: InsuranceFund = env.fetch(insurance_fund_address()).await;
+    assert_eq!(fund.total_collected, fund_before.total_collected + premium);
+
+    // Nothing is payable while the grant is in good standing.
+    assert_dge_error(env.claim_coverage(grant_address, &co_funder).await, DGEError::GrantNotLiquidated);
+
+    env.payout(grant_address, 2, FAILING_METRIC, 2).await.unwrap();
+    let holder_before = env.balance(co_funder.pubkey()).await;
+    let policy_rent = env.balance(coverage_address(&grant_address, &co_funder.pubkey())).await;
+    env.claim_coverage(grant_address, &co_funder).await.unwrap();
+
+    assert_eq!(env.balance(co_funder.pubkey()).await, holder_before + covered_amount + policy_rent);
+    let fund: InsuranceFund = env.fetch(insurance_fund_address()).await;
+    assert_eq!(fund.total_withdrawn, covered_amount);
+    assert_eq!(fund.outstanding_coverage, 0);
+    // The policy is closed, so it cannot be claimed twice.
+    assert!(env.claim_coverage(grant_address, &co_funder).await.is_err());
+    assert_dge_error(env.purchase_coverage(grant_address, &co_funder, 1).await, DGEError::GrantLiquidated);
+}
+
+#[tokio::test]
+// Test case: Cover is bounded per co-funder and per grant, closes once a milestone misses its pass
+// score, and policies share what was left undisbursed at liquidation pro rata.
+async fn test_coverage_is_capped_and_paid_pro_rata() {
+    let mut env = TestEnv::new().await;
+    let mut params = ConfigParams::defaults();
+    params.insurance_fee_bps = INSURANCE_FEE_BPS;
+    params.coverage_premium_bps = 1;
+    params.pass_score = 90;
+    params.partial_payout_floor = Some(60);
+    env.update_config(params).await.unwrap();
+    let grant_address = env.initialize_grant().await;
+
+    let first = env.challenger.insecure_clone();
+    let second = Keypair::new();
+    let late = Keypair::new();
+    for holder in [&second, &late] {
+        env.transfer_from_dao(holder.pubkey(), 10 * LAMPORTS_PER_SOL).await;
+    }
+    for holder in [&first, &second, &late] {
+        env.record_co_funding(grant_address, holder.pubkey(), 2_000 * LAMPORTS_PER_SOL).await.unwrap();
+    }
+
+    // Each co-funder is capped at their contribution, all cover together at the grant's exposure.
+    let first_cover = 2_000 * LAMPORTS_PER_SOL;
+    let second_cover = TOTAL_GRANT - first_cover;
+    assert_dge_error(env.purchase_coverage(grant_address, &first, first_cover + 1).await, DGEError::InvalidCoverageAmount);
+    env.purchase_coverage(grant_address, &first, first_cover).await.unwrap();
+    assert_dge_error(env.purchase_coverage(grant_address, &second, second_cover + 1).await, DGEError::InvalidCoverageAmount);
+    env.purchase_coverage(grant_address, &second, second_cover).await.unwrap();
+    assert_eq!(env.fetch::<Grant>(grant_address).await.total_covered, TOTAL_GRANT);
+
+    // A milestone short of its pass score closes the grant to new cover.
+    env.payout(grant_address, 1, PASSING_METRIC, 2).await.unwrap();
+    env.payout(grant_address, 2, GOOD_METRIC, 2).await.unwrap();
+    assert!(env.fetch::<Grant>(grant_address).await.missed_pass_score);
+    assert_dge_error(env.purchase_coverage(grant_address, &late, LAMPORTS_PER_SOL).await, DGEError::CoverageClosed);
+
+    // Only a third of the grant was left at liquidation, so each policy pays a third of its cover.
+    env.payout(grant_address, 3, FAILING_METRIC, 2).await.unwrap();
+    let grant: Grant = env.fetch(grant_address).await;
+    let exposure = grant.total_grant_amount - grant.amount_disbursed;
+    assert_eq!(exposure, TOTAL_GRANT - 2 * (TOTAL_GRANT / TRANCHES as u64));
+    env.transfer_from_dao(insurance_fund_address(), TOTAL_GRANT).await;
+
+    let mut paid = 0;
+    for (holder, cover) in [(&first, first_cover), (&second, second_cover)] {
+        let expected = (cover as u128 * exposure as u128 / TOTAL_GRANT as u128) as u64;
+        assert_eq!(grant.coverage_payout_for(cover).unwrap(), expected);
+        let holder_before = env.balance(holder.pubkey()).await;
+        let policy_rent = env.balance(coverage_address(&grant_address, &holder.pubkey())).await;
+        env.claim_coverage(grant_address, holder).await.unwrap();
+        assert_eq!(env.balance(holder.pubkey()).await, holder_before + expected + policy_rent);
+        paid += expected;
+    }
+    assert!(paid <= exposure);
+}
+
+#[tokio::test]
+// Test case: A rejected challenge freezes payouts, pays the stake to the builder, and closes the Challenge.
+async fn test_rejected_challenge_pays_builder_and_resumes() {
+    let mut env = TestEnv::new().await;
+    let grant_address = env.initialize_grant().await;
+    env.payout(grant_address, 1, PASSING_METRIC, 2).await.unwrap();
+
+    assert_dge_error(
+        env.challenge(grant_address, 1, DEFAULT_MIN_CHALLENGE_STAKE - 1).await,
+        DGEError::ChallengeStakeTooLow,
+    );
+    env.challenge(grant_address, 1, DEFAULT_MIN_CHALLENGE_STAKE).await.unwrap();
+    let challenge: Challenge = env.fetch(challenge_address(&grant_address, 1)).await;
+    assert_eq!(challenge.stake, DEFAULT_MIN_CHALLENGE_STAKE);
+    assert_dge_error(env.payout(grant_address, 2, PASSING_METRIC, 2).await, DGEError::GrantFrozen);
+
+    let builder_before = env.balance(env.builder.pubkey()).await;
+    env.resolve(grant_address, 1, false).await.unwrap();
+    assert_eq!(env.balance(env.builder.pubkey()).await, builder_before + DEFAULT_MIN_CHALLENGE_STAKE);
+    assert!(env
+        .context
+        .banks_client
+        .get_account(challenge_address(&grant_address, 1))
+        .await
+        .unwrap()
+        .is_none());
+
+    env.payout(grant_address, 2, PASSING_METRIC, 2).await.unwrap();
+}
+
+#[tokio::test]
+// Test case: An upheld challenge liquidates the grant, refunds the challenger's stake, and
+// pays the challenger 20% of the Builder Bond.
+async fn test_upheld_challenge_liquidates_grant() {
+    let mut env = TestEnv::new().await;
+    let grant_address = env.initialize_grant().await;
+    env.payout(grant_address, 1, PASSING_METRIC, 2).await.unwrap();
+
+    let challenger_before = env.balance(env.challenger.pubkey()).await;
+    env.challenge(grant_address, 1, DEFAULT_MIN_CHALLENGE_STAKE).await.unwrap();
+    env.resolve(grant_address, 1, true).await.unwrap();
+
+    // Closing the Challenge returns both the stake and its rent (the bank payer covers fees).
+    assert_eq!(env.balance(env.challenger.pubkey()).await, challenger_before);
+    let grant: Grant = env.fetch(grant_address).await;
+    assert!(grant.is_liquidated);
+    assert!(!grant.is_frozen);
+    assert_dge_error(env.payout(grant_address, 2, PASSING_METRIC, 2).await, DGEError::GrantLiquidated);
+    // The overturned milestone's receipt no longer counts as proof of delivery.
+    let receipt: TrancheReceipt = env.fetch(TrancheReceipt::address(&grant_address, 1).0).await;
+    assert!(receipt.is_revoked);
+
+    let reward = TIER_1_BOND_AT_ONE_DOLLAR * DEFAULT_CHALLENGER_BOND_SHARE_BPS as u64 / 10_000;
+    let challenger_account: TokenAccount = env.fetch(env.challenger_fst_account).await;
+    assert_eq!(challenger_account.amount, reward);
+    assert_eq!(grant.builder_bond_amount, TIER_1_BOND_AT_ONE_DOLLAR - reward);
+    let vault: TokenAccount = env.fetch(bond_vault_address(&env.fst_mint)).await;
+    assert_eq!(vault.amount, TIER_1_BOND_AT_ONE_DOLLAR - reward);
+}
+
+#[tokio::test]
+// Test case: A grant liquidated by governance while a challenge is open is not counted twice
+// when the challenge is then upheld.
+async fn test_upheld_challenge_after_liquidation() {
+    let mut env = TestEnv::new().await;
+    let grant_address = env.initialize_grant().await;
+    env.payout(grant_address, 1, PASSING_METRIC, 2).await.unwrap();
+    env.challenge(grant_address, 1, DEFAULT_MIN_CHALLENGE_STAKE).await.unwrap();
+
+    env.liquidate_bond(grant_address).await.unwrap();
+    env.resolve(grant_address, 1, true).await.unwrap();
+    let profile: BuilderProfile = env.fetch(builder_profile_address(&env.builder.pubkey())).await;
+    assert_eq!(profile.liquidations, 1);
+}
+
+#[tokio::test]
+// Test case: The challenge window closes `challenge_window_seconds` after the payout.
+async fn test_challenge_window_closes() {
+    let mut env = TestEnv::new().await;
+    let grant_address = env.initialize_grant().await;
+    env.payout(grant_address, 1, PASSING_METRIC, 2).await.unwrap();
+
+    env.advance_clock(DEFAULT_CHALLENGE_WINDOW_SECONDS + 1).await;
+    assert_dge_error(
+        env.challenge(grant_address, 1, DEFAULT_MIN_CHALLENGE_STAKE).await,
+        DGEError::ChallengeWindowClosed,
+    );
+
+    // The next payout reopens the window for the new milestone only.
+    env.payout(grant_address, 2, PASSING_METRIC, 2).await.unwrap();
+    assert_dge_error(
+        env.challenge(grant_address, 1, DEFAULT_MIN_CHALLENGE_STAKE).await,
+        DGEError::MilestoneNotChallengeable,
+    );
+    env.challenge(grant_address, 2, DEFAULT_MIN_CHALLENGE_STAKE).await.unwrap();
+}
+
+#[test]
+// Test case: Every Anchor error code decodes back to its variant and onto a stable DGE code.
+fn test_error_codes_decode() {
+    for (index, error) in DGEError::ALL.iter().enumerate() {
+        let anchor_code = u32::from(*error);
+        assert_eq!(anchor_code, anchor_lang::error::ERROR_CODE_OFFSET + index as u32);
+        assert_eq!(DGEError::from_anchor_code(anchor_code), Some(*error));
+    }
+    assert_eq!(DGEError::from_anchor_code(0), None);
+    assert_eq!(DgeErrorCode::from(DGEError::GrantFrozen).code(), 204);
+    assert_eq!(DgeErrorCode::from_code(204), Some(DgeErrorCode::GrantFrozen));
+}
+
+#[tokio::test]
+// Test case: A completed grant's bond goes back to the builder once the last challenge window
+// has closed, and only once.
+async fn test_bond_released_after_completion() {
+    let mut env = TestEnv::new().await;
+    let grant_address = env.initialize_grant().await;
+    env.payout(grant_address, 1, PASSING_METRIC, 2).await.unwrap();
+    assert_dge_error(env.release_bond(grant_address).await, DGEError::BondLocked);
+
+    env.payout(grant_address, 2, PASSING_METRIC, 2).await.unwrap();
+    env.payout(grant_address, 3, PASSING_METRIC, 2).await.unwrap();
+    // The last milestone can still be challenged, which freezes the bond as well.
+    assert_dge_error(env.release_bond(grant_address).await, DGEError::BondLocked);
+    env.challenge(grant_address, 3, DEFAULT_MIN_CHALLENGE_STAKE).await.unwrap();
+    assert_dge_error(env.release_bond(grant_address).await, DGEError::GrantFrozen);
+    env.resolve(grant_address, 3, false).await.unwrap();
+
+    env.advance_clock(DEFAULT_CHALLENGE_WINDOW_SECONDS + 1).await;
+    env.release_bond(grant_address).await.unwrap();
+    let builder_account: TokenAccount = env.fetch(env.builder_fst_account).await;
+    assert_eq!(builder_account.amount, BUILDER_FST_BALANCE);
+    assert_eq!(env.fetch::<Grant>(grant_address).await.builder_bond_amount, 0);
+    assert_dge_error(env.release_bond(grant_address).await, DGEError::BondLocked);
+}
+
+#[tokio::test]
+// Test case: Governance sweeps a liquidated grant's bond to the treasury; the builder cannot
+// release it.
+async fn test_liquidated_bond_swept_to_treasury() {
+    let mut env = TestEnv::new().await;
+    let dao = env.dao.insecure_clone();
+    let grant_address = env.initialize_grant().await;
+    assert_dge_error(env.sweep_liquidated_bond(grant_address, &dao).await, DGEError::GrantNotLiquidated);
+
+    env.payout(grant_address, 1, FAILING_METRIC, 2).await.unwrap();
+    assert_dge_error(env.release_bond(grant_address).await, DGEError::GrantLiquidated);
+    let stranger = env.challenger.insecure_clone();
+    assert_dge_error(env.sweep_liquidated_bond(grant_address, &stranger).await, DGEError::Unauthorized);
+
+    env.sweep_liquidated_bond(grant_address, &dao).await.unwrap();
+    let treasury_account: TokenAccount = env.fetch(env.treasury_fst_account).await;
+    assert_eq!(treasury_account.amount, TIER_1_BOND_AT_ONE_DOLLAR);
+    let vault: TokenAccount = env.fetch(bond_vault_address(&env.fst_mint)).await;
+    assert_eq!(vault.amount, 0);
+    assert_dge_error(env.sweep_liquidated_bond(grant_address, &dao).await, DGEError::BondLocked);
+}
+
+#[tokio::test]
+// Test case: Rotating the builder authority needs governance, after which only the new key can
+// claim milestones.
+async fn test_rotate_builder_authority() {
+    let mut env = TestEnv::new().await;
+    let dao = env.dao.insecure_clone();
+    let grant_address = env.initialize_grant().await;
+    env.payout(grant_address, 1, PASSING_METRIC, 2).await.unwrap();
+
+    let new_builder = Keypair::new();
+    let stranger = env.challenger.insecure_clone();
+    assert_dge_error(
+        env.rotate_builder_authority(grant_address, new_builder.pubkey(), &stranger).await,
+        DGEError::Unauthorized,
+    );
+    let old_builder = env.builder.pubkey();
+    assert_dge_error(
+        env.rotate_builder_authority(grant_address, old_builder, &dao).await,
+        DGEError::InvalidBuilderAuthority,
+    );
+    env.rotate_builder_authority(grant_address, new_builder.pubkey(), &dao).await.unwrap();
+    assert_eq!(env.fetch::<Grant>(grant_address).await.builder, new_builder.pubkey());
+
+    assert_error_code(
+        env.payout(grant_address, 2, PASSING_METRIC, 2).await,
+        anchor_lang::error::ErrorCode::ConstraintHasOne.into(),
+    );
+
+    env.transfer_from_dao(new_builder.pubkey(), LAMPORTS_PER_SOL).await;
+    env.builder = new_builder;
+    env.payout(grant_address, 2, PASSING_METRIC, 2).await.unwrap();
+    assert_eq!(env.fetch::<Grant>(grant_address).await.tranches_completed, 2);
+}
+
+#[tokio::test]
+// Test case: The standing check returns a snapshot for healthy and unfunded grants and fails for
+// liquidated ones.
+async fn test_verify_grant_standing() {
+    let mut env = TestEnv::new().await;
+    let grant_address = env.initialize_grant().await;
+    env.payout(grant_address, 1, PASSING_METRIC, 2).await.unwrap();
+    let status = env.grant_standing(grant_address).await.unwrap();
+    assert_eq!(status, env.fetch::<Grant>(grant_address).await.status());
+    assert_eq!(status.builder, env.builder.pubkey());
+    assert_eq!(status.tranches_completed, 1);
+    assert_eq!(status.total_tranches, TRANCHES);
+    assert!(status.is_funded);
+    assert!(!status.is_complete);
+    assert!(!status.is_frozen);
+
+    // An unfunded grant is still in good standing; callers see that nothing is escrowed yet.
+    let unfunded_grant = env.initialize_grant_with_bond(TIER_1_BOND_AT_ONE_DOLLAR).await.unwrap();
+    let status = env.grant_standing(unfunded_grant).await.unwrap();
+    assert!(!status.is_funded);
+    assert_eq!(status.amount_disbursed, 0);
+
+    let liquidated_grant = env.initialize_grant().await;
+    env.payout(liquidated_grant, 1, FAILING_METRIC, 2).await.unwrap();
+    assert_dge_error(env.grant_standing(liquidated_grant).await.map(|_| ()), DGEError::GrantLiquidated);
+}
+
+#[tokio::test]
+// Test case: Governance manages the verifier set, the threshold stays within the set, and a removed
+// verifier no longer counts towards a payout's quorum.
+async fn test_verifier_set_management() {
+    let mut env = TestEnv::new().await;
+    let dao = env.dao.insecure_clone();
+    let stranger = env.challenger.insecure_clone();
+    let grant_address = env.initialize_grant().await;
+
+    let new_verifier = Keypair::new().pubkey();
+    let add = |verifier| depth_grant::instruction::AddVerifier { verifier };
+    let remove = |verifier| depth_grant::instruction::RemoveVerifier { verifier };
+    let set_threshold = |threshold| depth_grant::instruction::SetVerifierThreshold { threshold };
+    assert_dge_error(env.configure_verifier_set(add(new_verifier), &stranger).await, DGEError::Unauthorized);
+    assert_dge_error(env.configure_verifier_set(set_threshold(1), &stranger).await, DGEError::Unauthorized);
+
+    // The threshold can never exceed the number of verifiers.
+    assert_dge_error(env.configure_verifier_set(set_threshold(4), &dao).await, DGEError::InvalidVerifierThreshold);
+    env.configure_verifier_set(add(new_verifier), &dao).await.unwrap();
+    env.configure_verifier_set(set_threshold(4), &dao).await.unwrap();
+    assert_dge_error(env.configure_verifier_set(remove(new_verifier), &dao).await, DGEError::InvalidVerifierThreshold);
+    env.configure_verifier_set(set_threshold(2), &dao).await.unwrap();
+    env.configure_verifier_set(remove(new_verifier), &dao).await.unwrap();
+
+    let removed = env.verifiers[0].pubkey();
+    assert_dge_error(env.configure_verifier_set(remove(removed), &stranger).await, DGEError::Unauthorized);
+    env.configure_verifier_set(remove(removed), &dao).await.unwrap();
+    assert_dge_error(env.configure_verifier_set(remove(removed), &dao).await, DGEError::VerifierNotFound);
+    let verifier_set: VerifierSet = env.fetch(verifier_set_address()).await;
+    assert_eq!(verifier_set.verifiers.len(), 2);
+    assert_eq!(verifier_set.threshold, 2);
+
+    // The removed verifier's co-signature leaves the payout one attestation short.
+    assert_dge_error(
+        env.payout(grant_address, 1, PASSING_METRIC, 2).await,
+        DGEError::InsufficientVerifierSignatures,
+    );
+    env.verifiers.remove(0);
+    env.payout(grant_address, 1, PASSING_METRIC, 2).await.unwrap();
+}
+
+#[tokio::test]
+// Test case: Once a registry bucket is full, new grants go to a freshly opened bucket and the full
+// one is refused.
+async fn test_registry_bucket_rollover() {
+    let mut env = TestEnv::new().await;
+    assert_dge_error(env.open_registry_bucket().await, DGEError::RegistryBucketNotNeeded);
+
+    // Fill all but the last slot of the first bucket directly instead of opening 127 grants.
+    let prefilled = GRANTS_PER_REGISTRY_BUCKET - 1;
+    let mut registry: Registry = env.fetch(registry_address()).await;
+    registry.grant_count = prefilled as u64;
+    env.overwrite(registry_address(), &registry).await;
+    let mut bucket: RegistryBucket = env.fetch(RegistryBucket::address(0).0).await;
+    bucket.grants = (0..prefilled).map(|_| Pubkey::new_unique()).collect();
+    env.overwrite(RegistryBucket::address(0).0, &bucket).await;
+
+    let last_grant = env.initialize_grant_with_bond(TIER_1_BOND_AT_ONE_DOLLAR).await.unwrap();
+    let bucket: RegistryBucket = env.fetch(RegistryBucket::address(0).0).await;
+    assert_eq!(bucket.grants.len(), GRANTS_PER_REGISTRY_BUCKET);
+    assert_eq!(bucket.grants.last(), Some(&last_grant));
+
+    // The next grant belongs in bucket 1, which does not exist yet, and the full bucket 0 is refused.
+    assert_error_code(
+        env.initialize_grant_with_bond(TIER_1_BOND_AT_ONE_DOLLAR).await.map(|_| ()),
+        anchor_lang::error::ErrorCode::AccountNotInitialized.into(),
+    );
+    let builder = env.builder.insecure_clone();
+    let oracle = env.oracle.insecure_clone();
+    let now = env.now().await;
+    let post_price = env.post_fst_price_instruction(FST_PRICE_ONE_DOLLAR);
+    let (_, mut into_full_bucket) =
+        env.initialize_grant_instruction(TIER_1_BOND_AT_ONE_DOLLAR, TRANCHES, now, now + FUNDING_WINDOW_SECONDS).await;
+    let next_bucket = RegistryBucket::address(1).0;
+    for account in into_full_bucket.accounts.iter_mut().filter(|account| account.pubkey == next_bucket) {
+        account.pubkey = RegistryBucket::address(0).0;
+    }
+    assert_error_code(
+        env.send(&[post_price, into_full_bucket], &[&builder, &oracle]).await,
+        anchor_lang::error::ErrorCode::ConstraintSeeds.into(),
+    );
+
+    env.open_registry_bucket().await.unwrap();
+    assert_dge_error(env.open_registry_bucket().await, DGEError::RegistryBucketNotNeeded);
+    let grant_address = env.initialize_grant_with_bond(TIER_1_BOND_AT_ONE_DOLLAR).await.unwrap();
+    let grant: Grant = env.fetch(grant_address).await;
+    assert_eq!(grant.registry_index, GRANTS_PER_REGISTRY_BUCKET as u64);
+    assert_eq!(grant.registry_bucket_index(), 1);
+    let bucket: RegistryBucket = env.fetch(next_bucket).await;
+    assert_eq!(bucket.bucket_index, 1);
+    assert_eq!(bucket.grants, vec![grant_address]);
+    assert_eq!(env.fetch::<Registry>(registry_address()).await.bucket_count, 2);
+}
+
+#[tokio::test]
+// Test case: Governance withdraws from the InsuranceFund, but never into its rent reserve or the
+// cover it still owes; cover on a settled grant expires and frees its reserve.
+async fn test_insurance_fund_withdrawal() {
+    let mut env = TestEnv::new().await;
+    let dao = env.dao.insecure_clone();
+    let co_funder = env.challenger.insecure_clone();
+    let grant_address = env.initialize_grant().await;
+    env.transfer_from_dao(insurance_fund_address(), 50 * LAMPORTS_PER_SOL).await;
+
+    let recipient = Pubkey::new_unique();
+    assert_dge_error(env.withdraw_insurance_fund(recipient, LAMPORTS_PER_SOL, &co_funder).await, DGEError::Unauthorized);
+
+    let covered_amount = 10 * LAMPORTS_PER_SOL;
+    env.record_co_funding(grant_address, co_funder.pubkey(), TOTAL_GRANT).await.unwrap();
+    env.purchase_coverage(grant_address, &co_funder, covered_amount).await.unwrap();
+    let fund: InsuranceFund = env.fetch(insurance_fund_address()).await;
+    assert_eq!(fund.outstanding_coverage, covered_amount);
+
+    // Only what exceeds the rent reserve and the outstanding cover can be withdrawn.
+    let rent_minimum = env.context.banks_client.get_rent().await.unwrap().minimum_balance(8 + InsuranceFund::LEN);
+    let available = env.balance(insurance_fund_address()).await - rent_minimum - covered_amount;
+    assert_dge_error(
+        env.withdraw_insurance_fund(recipient, available + 1, &dao).await,
+        DGEError::InsufficientInsuranceFunds,
+    );
+    env.withdraw_insurance_fund(recipient, available, &dao).await.unwrap();
+    assert_eq!(env.balance(recipient).await, available);
+    assert_eq!(env.balance(insurance_fund_address()).await, rent_minimum + covered_amount);
+    let fund_after: InsuranceFund = env.fetch(insurance_fund_address()).await;
+    assert_eq!(fund_after.total_withdrawn, fund.total_withdrawn + available);
+
+    // The cover stays reserved until the grant can no longer be liquidated.
+    env.payout(grant_address, 1, PASSING_METRIC, 2).await.unwrap();
+    env.payout(grant_address, 2, PASSING_METRIC, 2).await.unwrap();
+    env.payout(grant_address, 3, PASSING_METRIC, 2).await.unwrap();
+    assert_dge_error(env.expire_coverage(grant_address, co_funder.pubkey()).await, DGEError::CoverageStillActive);
+    env.advance_clock(DEFAULT_CHALLENGE_WINDOW_SECONDS + 1).await;
+    env.expire_coverage(grant_address, co_funder.pubkey()).await.unwrap();
+    assert_eq!(env.fetch::<InsuranceFund>(insurance_fund_address()).await.outstanding_coverage, 0);
+
+    let fund_balance = env.balance(insurance_fund_address()).await;
+    env.withdraw_insurance_fund(recipient, fund_balance - rent_minimum, &dao).await.unwrap();
+    assert_eq!(env.balance(insurance_fund_address()).await, rent_minimum);
+    assert_dge_error(env.withdraw_insurance_fund(recipient, 1, &dao).await, DGEError::InsufficientInsuranceFunds);
+}
+
+#[tokio::test]
+// Test case: Another program cannot wrap a milestone payout in a CPI.
+async fn test_payout_rejects_cpi() {
+    let mut env = TestEnv::new().await;
+    let grant_address = env.initialize_grant().await;
+
+    let (payout, signers) = env.payout_instruction(grant_address, 1, PASSING_METRIC, 2, Vec::new()).await;
+    let signers: Vec<&Keypair> = signers.iter().collect();
+    assert_dge_error(env.send(&[wrap_in_cpi(payout.clone())], &signers).await, DGEError::UnexpectedCpi);
+    assert_eq!(env.fetch::<Grant>(grant_address).await.tranches_completed, 0);
+
+    env.send(&[payout], &signers).await.unwrap();
+    assert_eq!(env.fetch::<Grant>(grant_address).await.tranches_completed, 1);
+}
+
+#[tokio::test]
+// Test case: A liquidating payout emits `DMetricLiquidation`, which a paid tranche never does.
+async fn test_d_metric_liquidation_event() {
+    let mut env = TestEnv::new().await;
+    let grant_address = env.initialize_grant().await;
+
+    let (payout, signers) = env.payout_instruction(grant_address, 1, PASSING_METRIC, 2, Vec::new()).await;
+    let signers: Vec<&Keypair> = signers.iter().collect();
+    assert!(env.simulate_events::<DMetricLiquidation>(std::slice::from_ref(&payout), &signers).await.is_empty());
+    env.send(&[payout], &signers).await.unwrap();
+
+    let (payout, signers) = env.payout_instruction(grant_address, 2, FAILING_METRIC, 2, Vec::new()).await;
+    let signers: Vec<&Keypair> = signers.iter().collect();
+    let events = env.simulate_events::<DMetricLiquidation>(std::slice::from_ref(&payout), &signers).await;
+    let required_score = env.fetch::<MilestoneSchedule>(milestone_schedule_address(&grant_address)).await.required_scores[1];
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].grant, grant_address);
+    assert_eq!(events[0].milestone_index, 2);
+    assert_eq!(events[0].required_score, required_score);
+    assert!(events[0].d_metric_score < required_score);
+
+    env.send(&[payout], &signers).await.unwrap();
+    assert!(env.fetch::<Grant>(grant_address).await.is_liquidated);
+}